@@ -1,55 +1,127 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
+};
 use cw2::set_contract_version;
 
 // Correção: adicionar prefixos corretos nos imports
 use crate::error::ContractError;
 use crate::handlers::execute;
+use crate::handlers::migrate;
 use crate::handlers::query;
+use crate::handlers::reply;
 use crate::msg::execute::ExecuteMsg;
 use crate::msg::instantiate::InstantiateMsg;
+use crate::msg::migrate::MigrateMsg;
 use crate::msg::query::QueryMsg;
-use crate::state::model::Game;
-use crate::state::storage::GAMES;
+use crate::state::model::{Config, Ownership};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:increment";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:increment";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// Rewards can be claimed for 30 days after the last credit by default
+pub const DEFAULT_CLAIM_PERIOD: u64 = 30 * 24 * 60 * 60;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    use crate::state::storage::{RANK, TOTAL};
+    use crate::state::storage::{
+        ACHIEVEMENT_COUNT, CLUB_COUNT, CONFIG, OWNERSHIP, RANKED_PLAYERS, SEASON_COUNT,
+        SESSION_COUNT, SUBMITTERS, TOTAL, TOURNAMENT_COUNT,
+    };
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // Inicializar o estado
-    TOTAL.save(deps.storage, &0u64)?;
-    RANK.save(deps.storage, &Vec::new())?;
-    GAMES.save(
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+    OWNERSHIP.save(
         deps.storage,
-        info.sender.clone(),
-        &Game {
-            score: 0,
-            game_time: 0,
+        &Ownership {
+            owner: Some(owner.clone()),
+            pending_owner: None,
+            pending_expiry: None,
         },
     )?;
+    let rank_policy = msg.rank_policy.unwrap_or_default();
+    let session_limits = msg.session_limits.unwrap_or_default();
+    execute::validate_session_limits(&session_limits)?;
+    let game_rules = msg.game_rules.unwrap_or_default();
+    execute::validate_game_rules(&game_rules)?;
+    let reward_token = msg
+        .reward_token
+        .map(|token| deps.api.addr_validate(&token))
+        .transpose()?;
+    if let Some(emission) = &msg.emission {
+        execute::validate_emission(emission, reward_token.as_ref())?;
+    }
+    let house_fee = msg.house_fee.unwrap_or_default();
+    let treasury = msg
+        .treasury
+        .map(|treasury| deps.api.addr_validate(&treasury))
+        .transpose()?;
+    execute::validate_house_fee(house_fee, treasury.as_ref())?;
+    let achievement_nft = msg
+        .achievement_nft
+        .map(|nft| deps.api.addr_validate(&nft))
+        .transpose()?;
+    let streak = msg.streak.unwrap_or_default();
+    execute::validate_streak_config(&streak, treasury.as_ref())?;
+    let referral_share = msg.referral_share.unwrap_or_default();
+    execute::validate_referral_share(referral_share)?;
+    if let Some(max_games_per_block) = msg.max_games_per_block {
+        execute::validate_submission_ceiling(max_games_per_block)?;
+    }
+    let payout_table = msg.payout_table.unwrap_or_default();
+    execute::validate_payout_table(&payout_table)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            rank_policy,
+            server_key: msg.server_key,
+            session_limits,
+            game_rules,
+            payout_table,
+            claim_period: msg.claim_period.unwrap_or(DEFAULT_CLAIM_PERIOD),
+            reward_token,
+            emission: msg.emission,
+            house_fee,
+            treasury,
+            achievement_nft,
+            streak,
+            referral_share,
+            max_games_per_block: msg.max_games_per_block,
+        },
+    )?;
+    for submitter in msg.submitters.unwrap_or_default() {
+        let submitter = deps.api.addr_validate(&submitter)?;
+        SUBMITTERS.save(deps.storage, submitter, &Empty {})?;
+    }
+    TOTAL.save(deps.storage, &0u64)?;
+    SESSION_COUNT.save(deps.storage, &0u64)?;
+    RANKED_PLAYERS.save(deps.storage, &0u64)?;
+    SEASON_COUNT.save(deps.storage, &0u64)?;
+    TOURNAMENT_COUNT.save(deps.storage, &0u64)?;
+    CLUB_COUNT.save(deps.storage, &0u64)?;
+    ACHIEVEMENT_COUNT.save(deps.storage, &0u64)?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", owner))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
@@ -57,17 +129,256 @@ pub fn execute(
             player,
             score,
             game_time,
-        } => execute::new_game(deps, player, score, game_time),
+            game_id,
+        } => execute::new_game(deps, env, info, player, score, game_time, game_id),
+        ExecuteMsg::SubmitSignedGame {
+            score,
+            game_time,
+            nonce,
+            signature,
+        } => execute::submit_signed_game(deps, env, info, score, game_time, nonce, signature),
+        ExecuteMsg::StartGame {} => execute::start_game(deps, env, info),
+        ExecuteMsg::EndGame { session_id, score } => {
+            execute::end_game(deps, env, info, session_id, score)
+        }
+        ExecuteMsg::AddSubmitter { address, game_id } => {
+            execute::add_submitter(deps, info, address, game_id)
+        }
+        ExecuteMsg::RemoveSubmitter { address, game_id } => {
+            execute::remove_submitter(deps, info, address, game_id)
+        }
+        ExecuteMsg::RegisterGame {
+            game_id,
+            name,
+            config,
+        } => execute::register_game(deps, info, game_id, name, config),
+        ExecuteMsg::SetServerKey { server_key } => execute::set_server_key(deps, info, server_key),
+        ExecuteMsg::SetSessionLimits { session_limits } => {
+            execute::set_session_limits(deps, info, session_limits)
+        }
+        ExecuteMsg::SetGameRules { game_rules } => execute::set_game_rules(deps, info, game_rules),
+        ExecuteMsg::CreateSeason { name, start, end } => {
+            execute::create_season(deps, env, info, name, start, end)
+        }
+        ExecuteMsg::FundSeason { season_id } => execute::fund_season(deps, info, season_id),
+        ExecuteMsg::FundSeasonClubPrize { season_id } => {
+            execute::fund_season_club_prize(deps, info, season_id)
+        }
+        ExecuteMsg::FinalizeSeason { season_id } => execute::finalize_season(deps, env, season_id),
+        ExecuteMsg::SetPayoutTable { payout_table } => {
+            execute::set_payout_table(deps, info, payout_table)
+        }
+        ExecuteMsg::ClaimRewards {} => execute::claim_rewards(deps, info),
+        ExecuteMsg::SweepRewards { players } => execute::sweep_rewards(deps, env, info, players),
+        ExecuteMsg::SetClaimPeriod { claim_period } => {
+            execute::set_claim_period(deps, info, claim_period)
+        }
+        ExecuteMsg::SetRewardToken { reward_token } => {
+            execute::set_reward_token(deps, info, reward_token)
+        }
+        ExecuteMsg::SetAchievementNft { nft } => execute::set_achievement_nft(deps, info, nft),
+        ExecuteMsg::AddAchievement {
+            name,
+            description,
+            rule,
+        } => execute::add_achievement(deps, info, name, description, rule),
+        ExecuteMsg::RemoveAchievement { id } => execute::remove_achievement(deps, info, id),
+        ExecuteMsg::SetStreakConfig { streak } => execute::set_streak_config(deps, info, streak),
+        ExecuteMsg::BuyStreakFreeze {} => execute::buy_streak_freeze(deps, env, info),
+        ExecuteMsg::RegisterReferrer { referrer } => {
+            execute::register_referrer(deps, info, referrer)
+        }
+        ExecuteMsg::SetReferralShare { share } => execute::set_referral_share(deps, info, share),
+        ExecuteMsg::CreateTournament {
+            entry_fee,
+            start,
+            end,
+            max_players,
+            min_players,
+            payout_table,
+        } => execute::create_tournament(
+            deps,
+            env,
+            info,
+            entry_fee,
+            start,
+            end,
+            max_players,
+            min_players,
+            payout_table,
+        ),
+        ExecuteMsg::JoinTournament { id } => execute::join_tournament(deps, env, info, id),
+        ExecuteMsg::SettleTournament { id } => execute::settle_tournament(deps, env, id),
+        ExecuteMsg::CancelTournament { id } => execute::cancel_tournament(deps, info, id),
+        ExecuteMsg::RefundTournament { id } => execute::refund_tournament(deps, env, info, id),
+        ExecuteMsg::CreateClub {
+            name,
+            open,
+            max_members,
+            scoring,
+        } => execute::create_club(deps, info, name, open, max_members, scoring),
+        ExecuteMsg::InviteToClub { id, player } => execute::invite_to_club(deps, info, id, player),
+        ExecuteMsg::JoinClub { id } => execute::join_club(deps, info, id),
+        ExecuteMsg::LeaveClub {} => execute::leave_club(deps, info),
+        ExecuteMsg::DepositToClub { id } => execute::deposit_to_club(deps, info, id),
+        ExecuteMsg::SetClubSplit { id, split } => execute::set_club_split(deps, info, id, split),
+        ExecuteMsg::DistributeClubTreasury { id } => {
+            execute::distribute_club_treasury(deps, env, info, id)
+        }
+        ExecuteMsg::SetHouseFee {
+            house_fee,
+            treasury,
+        } => execute::set_house_fee(deps, info, house_fee, treasury),
+        ExecuteMsg::SetEmission { emission } => execute::set_emission(deps, info, emission),
+        ExecuteMsg::Receive(wrapper) => execute::receive(deps, info, wrapper),
+        ExecuteMsg::TransferOwnership { new_owner, expiry } => {
+            execute::transfer_ownership(deps, env, info, new_owner, expiry)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, env, info),
+        ExecuteMsg::RenounceOwnership {} => execute::renounce_ownership(deps, info),
+        ExecuteMsg::GrantRole { role, address } => execute::grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute::revoke_role(deps, info, role, address),
+        ExecuteMsg::Pause {} => execute::pause(deps, env, info),
+        ExecuteMsg::Unpause {} => execute::unpause(deps, info),
+        ExecuteMsg::SetSubmissionCeiling {
+            max_games_per_block,
+        } => execute::set_submission_ceiling(deps, info, max_games_per_block),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetTotal {} => to_json_binary(&query::get_total(deps)?),
-        QueryMsg::GetRank {} => to_json_binary(&query::get_rank(deps)?),
-        QueryMsg::GetScoreByPlayer { player } => {
-            to_json_binary(&query::get_score_by_player(deps, player)?)
+        QueryMsg::GetTotal { game_id } => to_json_binary(&query::get_total(deps, game_id)?),
+        QueryMsg::ListGames { start_after, limit } => {
+            to_json_binary(&query::list_games(deps, start_after, limit)?)
+        }
+        QueryMsg::GetConfig {} => to_json_binary(&query::get_config(deps)?),
+        QueryMsg::GetOwnership {} => to_json_binary(&query::get_ownership(deps)?),
+        QueryMsg::HasRole { role, address } => {
+            to_json_binary(&query::has_role(deps, role, address)?)
+        }
+        QueryMsg::ListRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetPause {} => to_json_binary(&query::get_pause(deps)?),
+        QueryMsg::GetPlayerHistory {
+            player,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_player_history(
+            deps,
+            player,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetGame { record_id } => to_json_binary(&query::get_game(deps, record_id)?),
+        QueryMsg::GetPlayerStats { player } => {
+            to_json_binary(&query::get_player_stats(deps, player)?)
+        }
+        QueryMsg::GetSeason { season_id } => {
+            to_json_binary(&query::get_season(deps, env, season_id)?)
+        }
+        QueryMsg::GetSeasonPayouts { season_id } => {
+            to_json_binary(&query::get_season_payouts(deps, season_id)?)
+        }
+        QueryMsg::ListSeasons { start_after, limit } => {
+            to_json_binary(&query::list_seasons(deps, env, start_after, limit)?)
+        }
+        QueryMsg::GetTournament { id } => to_json_binary(&query::get_tournament(deps, id)?),
+        QueryMsg::ListTournaments { start_after, limit } => {
+            to_json_binary(&query::list_tournaments(deps, start_after, limit)?)
+        }
+        QueryMsg::GetClub {
+            id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_club(deps, id, start_after, limit)?),
+        QueryMsg::GetClubRank { start_after, limit } => {
+            to_json_binary(&query::get_club_rank(deps, start_after, limit)?)
+        }
+        QueryMsg::ListAchievements { start_after, limit } => {
+            to_json_binary(&query::list_achievements(deps, start_after, limit)?)
+        }
+        QueryMsg::GetPlayerAchievements {
+            player,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_player_achievements(
+            deps,
+            player,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetClubTreasury { id } => to_json_binary(&query::get_club_treasury(deps, id)?),
+        QueryMsg::GetClubPayouts {
+            id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_club_payouts(deps, id, start_after, limit)?),
+        QueryMsg::GetPlayerClub { player } => {
+            to_json_binary(&query::get_player_club(deps, player)?)
+        }
+        QueryMsg::GetReferral { player } => to_json_binary(&query::get_referral(deps, player)?),
+        QueryMsg::GetReferees {
+            referrer,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_referees(deps, referrer, start_after, limit)?),
+        QueryMsg::GetStreak { player } => to_json_binary(&query::get_streak(deps, env, player)?),
+        QueryMsg::GetTournamentRank {
+            id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_tournament_rank(deps, id, start_after, limit)?),
+        QueryMsg::GetEmissionStatus { player } => {
+            to_json_binary(&query::get_emission_status(deps, env, player)?)
+        }
+        QueryMsg::GetPendingRewards { player } => {
+            to_json_binary(&query::get_pending_rewards(deps, player)?)
+        }
+        QueryMsg::GetSession { session_id } => {
+            to_json_binary(&query::get_session(deps, session_id)?)
+        }
+        QueryMsg::GetSubmitters {
+            game_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_submitters(deps, game_id, start_after, limit)?),
+        QueryMsg::GetRank {
+            game_id,
+            season_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_rank(
+            deps,
+            game_id,
+            season_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetPlayerRank { player } => {
+            to_json_binary(&query::get_player_rank(deps, player)?)
+        }
+        QueryMsg::GetRankAround {
+            player,
+            above,
+            below,
+        } => to_json_binary(&query::get_rank_around(deps, player, above, below)?),
+        QueryMsg::GetScoreByPlayer { player, game_id } => {
+            to_json_binary(&query::get_score_by_player(deps, player, game_id)?)
         }
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate::migrate(deps, env)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    reply::reply(deps, env, msg)
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, OverflowError, StdError, VerificationError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,8 +6,203 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Verification(#[from] VerificationError),
+
+    #[error("{0}")]
+    Semver(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReply { id: u64 },
+
+    #[error("Cannot migrate from contract {contract}")]
+    WrongContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to older version {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("No ownership transfer pending")]
+    NoPendingTransfer {},
+
+    #[error("Ownership transfer expired")]
+    TransferExpired {},
+
+    #[error("Session {session_id} already closed")]
+    SessionClosed { session_id: u64 },
+
+    #[error("Session shorter than {min_duration} seconds")]
+    SessionTooShort { min_duration: u64 },
+
+    #[error("Session expired after {max_duration} seconds")]
+    SessionExpired { max_duration: u64 },
+
+    #[error("Invalid session limits")]
+    InvalidSessionLimits {},
+
+    #[error("Games must be played through StartGame and EndGame")]
+    SessionRequired {},
+
+    #[error("Score too high, max is {max}")]
+    ScoreTooHigh { max: u64 },
+
+    #[error("Invalid game time, must be between {min} and {max} seconds")]
+    InvalidGameTime { min: u64, max: u64 },
+
+    #[error("Invalid game rules")]
+    InvalidGameRules {},
+
+    #[error("Server key not set")]
+    ServerKeyNotSet {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Nonce {nonce} already used")]
+    NonceAlreadyUsed { nonce: u64 },
+
+    #[error("Invalid season, it must end after it starts and cannot start in the past")]
+    InvalidSeason {},
+
+    #[error("Season overlaps season {season_id}")]
+    SeasonOverlap { season_id: u64 },
+
+    #[error("Season {season_id} has not finished yet")]
+    SeasonNotFinished { season_id: u64 },
+
+    #[error("Season {season_id} already finalized")]
+    SeasonAlreadyFinalized { season_id: u64 },
+
+    #[error("Invalid payout table, shares must be positive and add up to at most 100%")]
+    InvalidPayoutTable {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("No pending rewards")]
+    NoPendingRewards {},
+
+    #[error("Rewards of {player} can still be claimed")]
+    RewardsNotExpired { player: String },
+
+    #[error("Swept rewards need a treasury or an owner to go to")]
+    NoSweepRecipient {},
+
+    #[error("Reward token not set")]
+    RewardTokenNotSet {},
+
+    #[error("Reward token already set")]
+    RewardTokenAlreadySet {},
+
+    #[error("Only the reward token is accepted")]
+    UnsupportedToken {},
+
+    #[error("Invalid emission schedule")]
+    InvalidEmission {},
+
+    #[error("Invalid house fee, it must be at most 100% and needs a treasury")]
+    InvalidHouseFee {},
+
+    #[error("Invalid tournament")]
+    InvalidTournament {},
+
+    #[error("Tournament {tournament_id} is closed")]
+    TournamentClosed { tournament_id: u64 },
+
+    #[error("Tournament {tournament_id} is full")]
+    TournamentFull { tournament_id: u64 },
+
+    #[error("Entry fee must be exactly {entry_fee}")]
+    WrongEntryFee { entry_fee: Coin },
+
+    #[error("Already joined tournament {tournament_id}")]
+    AlreadyJoined { tournament_id: u64 },
+
+    #[error("Not a player of tournament {tournament_id}")]
+    NotJoined { tournament_id: u64 },
+
+    #[error("Tournament {tournament_id} has not finished yet")]
+    TournamentNotFinished { tournament_id: u64 },
+
+    #[error("Tournament {tournament_id} cannot be settled, its entries are refunded")]
+    TournamentNotSettleable { tournament_id: u64 },
+
+    #[error("Tournament {tournament_id} entries cannot be refunded")]
+    NotRefundable { tournament_id: u64 },
+
+    #[error("Game {game_id} is not registered")]
+    UnknownGame { game_id: String },
+
+    #[error("Invalid game id")]
+    InvalidGameId {},
+
+    #[error("Invalid club")]
+    InvalidClub {},
+
+    #[error("Already a member of club {club_id}")]
+    AlreadyInClub { club_id: u64 },
+
+    #[error("Not a member of any club")]
+    NotInClub {},
+
+    #[error("Club {club_id} is full")]
+    ClubFull { club_id: u64 },
+
+    #[error("Club {club_id} is invite only")]
+    ClubInviteOnly { club_id: u64 },
+
+    #[error("The club owner can only leave as the last member")]
+    ClubOwnerCannotLeave {},
+
+    #[error("Invalid club split")]
+    InvalidClubSplit {},
+
+    #[error("Club {club_id} still holds funds in its treasury")]
+    ClubTreasuryNotEmpty { club_id: u64 },
+
+    #[error("Invalid achievement")]
+    InvalidAchievement {},
+
+    #[error("Invalid streak config")]
+    InvalidStreakConfig {},
+
+    #[error("Streak freezes are not for sale")]
+    StreakFreezeNotForSale {},
+
+    #[error("A streak freeze costs exactly {price}")]
+    WrongStreakFreezePrice { price: Coin },
+
+    #[error("No streak to freeze")]
+    NoActiveStreak {},
+
+    #[error("Cannot hold more than {max} streak freezes")]
+    TooManyStreakFreezes { max: u32 },
+
+    #[error("Invalid referral share, it must be at most 100%")]
+    InvalidReferralShare {},
+
+    #[error("Players cannot refer themselves or anyone who referred them")]
+    InvalidReferrer {},
+
+    #[error("Referrer already registered")]
+    AlreadyReferred {},
+
+    #[error("Referrers must be registered before the first game")]
+    AlreadyPlayed {},
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Invalid submission ceiling")]
+    InvalidSubmissionCeiling {},
+
+    #[error("The owner role changes hands with TransferOwnership")]
+    OwnerRole {},
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
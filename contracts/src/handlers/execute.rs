@@ -1,33 +1,2431 @@
-use cosmwasm_std::{Addr, DepsMut, Response};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, OverflowError, OverflowOperation, Response, StdResult,
+    Storage, SubMsg, Timestamp, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721_base::ExecuteMsg as Cw721ExecuteMsg;
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 
 use crate::error::ContractError;
-use crate::state::model::Game;
-use crate::state::storage::{GAMES, RANK, TOTAL};
+use crate::handlers::reply::MINT_ACHIEVEMENT_REPLY_ID;
+use crate::msg::execute::{AchievementMetadata, ReceiveMsg, ScoreAttestation, Trait};
+use crate::state::model::{
+    Achievement, AchievementRule, BlockGames, Club, ClubMember, ClubPayout, ClubScoring, ClubSplit,
+    DailyEmission, EmissionSchedule, Game, GameConfig, GameRankEntry, GameRules, Ownership, Pause,
+    PauseReason, PlayerAchievement, PlayerStats, RankEntry, RankPolicy, RegisteredGame, Role,
+    Season, SeasonClubMember, SeasonClubScore, SeasonPayout, SeasonRankEntry, SeasonStatus,
+    ServerKey, Session, SessionLimits, SignatureScheme, Streak, StreakConfig, TokenRewards,
+    Tournament, TournamentRankEntry, TournamentState,
+};
+use crate::state::storage::{
+    active_season, club_members, clubs, game_rank, rank, registered_game, role_members,
+    season_club_members, season_club_scores, season_rank, tournament_rank, update_score_counts,
+    ACHIEVEMENTS, ACHIEVEMENT_COUNT, BLOCK_GAMES, CLUB_COUNT, CLUB_INVITES, CLUB_PAYOUTS,
+    CLUB_TREASURIES, CONFIG, DEFAULT_GAME_ID, EMISSION_TODAY, GAMES, GAME_LAST_SCORES,
+    GAME_PLAYERS, GAME_SUBMITTERS, GAME_TOTALS, NONCES, OWNERSHIP, PAUSE, PENDING_REWARDS,
+    PENDING_TOKEN_REWARDS, PLAYER_ACHIEVEMENTS, PLAYER_CLUBS, PLAYER_EMISSION, PLAYER_STATS,
+    PLAYER_STREAKS, PLAYER_TOURNAMENTS, RANKED_PLAYERS, REFERRALS, REFERRAL_EARNINGS, REFERRERS,
+    REGISTERED_GAMES, REWARDS_CREDITED_AT, SEASONS, SEASON_CLUB_POOLS, SEASON_CLUB_WINNERS,
+    SEASON_COUNT, SEASON_PAYOUTS, SEASON_POOLS, SEASON_TOKEN_POOLS, SEASON_TOTALS, SESSIONS,
+    SESSION_COUNT, SUBMITTERS, TOTAL, TOURNAMENTS, TOURNAMENT_COUNT, TOURNAMENT_PLAYERS,
+};
+
+// Keeps FinalizeSeason within a bounded number of payouts
+const MAX_PAYOUT_POSITIONS: usize = 100;
+const MAX_GAME_ID_LENGTH: usize = 32;
+// Bounds the members read to rank a top-K club
+const MAX_CLUB_MEMBERS: u32 = 100;
+// Every achievement is checked on each game
+const MAX_ACHIEVEMENTS: usize = 50;
+const MAX_PODIUM: u32 = 10;
+const MAX_STREAK_FREEZES: u32 = 7;
+
+// Shared guard of every privileged message. The owner holds every role but
+// the score submitter, scores only come from the game backends
+pub fn assert_role(deps: Deps, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    let ownership = OWNERSHIP.load(deps.storage)?;
+    if role != Role::ScoreSubmitter && ownership.owner.as_ref() == Some(sender) {
+        return Ok(());
+    }
+    match role_members(role) {
+        Some(members) if members.has(deps.storage, sender.clone()) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+pub fn assert_not_paused(deps: Deps) -> Result<(), ContractError> {
+    if PAUSE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+// Counts the games recorded in the block. The one over the ceiling pauses the
+// contract instead of being recorded, as an error would revert the pause
+fn guard_submission(
+    storage: &mut dyn Storage,
+    env: &Env,
+) -> Result<Option<Response>, ContractError> {
+    if PAUSE.may_load(storage)?.is_some() {
+        return Err(ContractError::Paused {});
+    }
+    let max_games = match CONFIG.load(storage)?.max_games_per_block {
+        Some(max_games) => max_games,
+        None => return Ok(None),
+    };
+
+    let height = env.block.height;
+    let games = BLOCK_GAMES
+        .may_load(storage)?
+        .filter(|block| block.height == height)
+        .map_or(0, |block| block.games)
+        + 1;
+    if games > max_games {
+        PAUSE.save(
+            storage,
+            &Pause {
+                paused_at: env.block.time,
+                reason: PauseReason::SubmissionCeiling,
+            },
+        )?;
+        return Ok(Some(
+            Response::new()
+                .add_attribute("action", "pause")
+                .add_attribute("reason", "submission_ceiling"),
+        ));
+    }
+    BLOCK_GAMES.save(storage, &BlockGames { height, games })?;
+    Ok(None)
+}
 
 pub fn new_game(
     deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    player: Addr,
+    score: u64,
+    game_time: u64,
+    game_id: Option<String>,
+) -> Result<Response, ContractError> {
+    if let Some(game_id) = registered_game(game_id) {
+        return record_registered_game(deps, &env, info, game_id, player, score, game_time);
+    }
+    assert_role(deps.as_ref(), &info.sender, Role::ScoreSubmitter)?;
+    assert_game_time_trusted(deps.as_ref())?;
+    if let Some(res) = guard_submission(deps.storage, &env)? {
+        return Ok(res);
+    }
+
+    record_game(deps, &env, player, score, game_time)
+}
+
+// NewGame and SubmitSignedGame take the game time on trust, from a submitter
+// or from the game server. Unless sessions_only is turned off, only EndGame,
+// which measures it on chain, records default game plays
+fn assert_game_time_trusted(deps: Deps) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.session_limits.sessions_only {
+        return Err(ContractError::SessionRequired {});
+    }
+    Ok(())
+}
+
+// Registered games only keep their own leaderboard, total and last scores,
+// seasons, tournaments and earnings follow the default game. They have no
+// sessions, so their game time always comes from their submitters
+fn record_registered_game(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    game_id: String,
     player: Addr,
     score: u64,
     game_time: u64,
 ) -> Result<Response, ContractError> {
+    let game = load_registered_game(deps.storage, &game_id)?;
+    if !GAME_SUBMITTERS.has(deps.storage, (&game_id, info.sender)) {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_game_rules(&game.config.game_rules, score, game_time)?;
+    if let Some(res) = guard_submission(deps.storage, env)? {
+        return Ok(res);
+    }
+
+    let total = GAME_TOTALS.load(deps.storage, &game_id)? + 1;
+    GAME_TOTALS.save(deps.storage, &game_id, &total)?;
+    GAME_LAST_SCORES.save(deps.storage, (&game_id, player.clone()), &score)?;
+
+    let key = (game_id.as_str(), player.clone());
+    let current = game_rank().may_load(deps.storage, key.clone())?;
+    let game_score = ranked_score(
+        game.config.rank_policy,
+        current.as_ref().map(|entry| entry.score),
+        score,
+    )?;
+    if current.as_ref().map(|entry| entry.score) != Some(game_score) {
+        game_rank().replace(
+            deps.storage,
+            key,
+            Some(&GameRankEntry {
+                game_id: game_id.clone(),
+                score: game_score,
+            }),
+            current.as_ref(),
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "new_game")
+        .add_attribute("game", game_id)
+        .add_attribute("player", player.to_string())
+        .add_attribute("score", score.to_string())
+        .add_attribute("game_time", game_time.to_string()))
+}
+
+pub fn register_game(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: String,
+    name: String,
+    config: GameConfig,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    validate_game_id(&game_id)?;
+    validate_game_rules(&config.game_rules)?;
+
+    if !REGISTERED_GAMES.has(deps.storage, &game_id) {
+        GAME_TOTALS.save(deps.storage, &game_id, &0)?;
+    }
+    REGISTERED_GAMES.save(deps.storage, &game_id, &RegisteredGame { name, config })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_game")
+        .add_attribute("game", game_id))
+}
+
+// Game ids end up in storage keys and event attributes
+fn validate_game_id(game_id: &str) -> Result<(), ContractError> {
+    if game_id == DEFAULT_GAME_ID
+        || game_id.is_empty()
+        || game_id.len() > MAX_GAME_ID_LENGTH
+        || !game_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ContractError::InvalidGameId {});
+    }
+    Ok(())
+}
+
+fn load_registered_game(
+    storage: &dyn Storage,
+    game_id: &str,
+) -> Result<RegisteredGame, ContractError> {
+    REGISTERED_GAMES
+        .may_load(storage, game_id)?
+        .ok_or_else(|| ContractError::UnknownGame {
+            game_id: game_id.to_string(),
+        })
+}
+
+pub fn submit_signed_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    score: u64,
+    game_time: u64,
+    nonce: u64,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    assert_game_time_trusted(deps.as_ref())?;
+    let config = CONFIG.load(deps.storage)?;
+    let server_key = config.server_key.ok_or(ContractError::ServerKeyNotSet {})?;
+    let player = info.sender;
+
+    if NONCES.has(deps.storage, (player.clone(), nonce)) {
+        return Err(ContractError::NonceAlreadyUsed { nonce });
+    }
+
+    let attestation = ScoreAttestation {
+        player: player.clone(),
+        score,
+        game_time,
+        nonce,
+        chain_id: env.block.chain_id.clone(),
+        contract_addr: env.contract.address.clone(),
+    };
+    let hash = attestation.hash()?;
+    let valid = match server_key.scheme {
+        SignatureScheme::Secp256k1 => {
+            deps.api
+                .secp256k1_verify(&hash, &signature, &server_key.pubkey)?
+        }
+        SignatureScheme::Ed25519 => {
+            deps.api
+                .ed25519_verify(&hash, &signature, &server_key.pubkey)?
+        }
+    };
+    if !valid {
+        return Err(ContractError::InvalidSignature {});
+    }
+    if let Some(res) = guard_submission(deps.storage, &env)? {
+        return Ok(res);
+    }
+
+    NONCES.save(deps.storage, (player.clone(), nonce), &Empty {})?;
+
+    let res = record_game(deps, &env, player, score, game_time)?;
+    Ok(res.add_attribute("nonce", nonce.to_string()))
+}
+
+pub fn start_game(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let session_id = SESSION_COUNT.load(deps.storage)? + 1;
+    SESSION_COUNT.save(deps.storage, &session_id)?;
+
+    SESSIONS.save(
+        deps.storage,
+        session_id,
+        &Session {
+            player: info.sender.clone(),
+            started_at: env.block.time,
+            ended_at: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_game")
+        .add_attribute("player", info.sender)
+        .add_attribute("session_id", session_id.to_string()))
+}
+
+pub fn end_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    session_id: u64,
+    score: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::ScoreSubmitter)?;
+    if let Some(res) = guard_submission(deps.storage, &env)? {
+        return Ok(res);
+    }
+
+    let mut session = SESSIONS.load(deps.storage, session_id)?;
+    if session.ended_at.is_some() {
+        return Err(ContractError::SessionClosed { session_id });
+    }
+
+    // The game time is the block time elapsed since StartGame
+    let limits = CONFIG.load(deps.storage)?.session_limits;
+    let game_time = env.block.time.seconds() - session.started_at.seconds();
+    if game_time < limits.min_duration {
+        return Err(ContractError::SessionTooShort {
+            min_duration: limits.min_duration,
+        });
+    }
+    if game_time > limits.max_duration {
+        return Err(ContractError::SessionExpired {
+            max_duration: limits.max_duration,
+        });
+    }
+
+    session.ended_at = Some(env.block.time);
+    SESSIONS.save(deps.storage, session_id, &session)?;
+
+    let res = record_game(deps, &env, session.player, score, game_time)?;
+    Ok(res.add_attribute("session_id", session_id.to_string()))
+}
+
+fn record_game(
+    deps: DepsMut,
+    env: &Env,
+    player: Addr,
+    score: u64,
+    game_time: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_game_rules(&config.game_rules, score, game_time)?;
+
     // UPDATE TOTAL GAMES
 
-    let total = TOTAL.load(deps.storage)?;
-    TOTAL.save(deps.storage, &(total + 1))?;
+    // The running total doubles as the record id of the new game
+    let record_id = TOTAL.load(deps.storage)? + 1;
+    TOTAL.save(deps.storage, &record_id)?;
 
     // UPDATE RANK
-    let mut rank = RANK.load(deps.storage)?;
-    rank.push((score, player.clone()));
-    rank.sort_by(|a, b| b.0.cmp(&a.0));
-    RANK.save(deps.storage, &rank)?;
+
+    let current = rank().may_load(deps.storage, player.clone())?;
+    let ranked_score = ranked_score(
+        config.rank_policy,
+        current.as_ref().map(|entry| entry.score),
+        score,
+    )?;
+    if current.is_none() {
+        RANKED_PLAYERS.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    }
+    if current.as_ref().map(|entry| entry.score) != Some(ranked_score) {
+        rank().replace(
+            deps.storage,
+            player.clone(),
+            Some(&RankEntry {
+                score: ranked_score,
+            }),
+            current.as_ref(),
+        )?;
+        update_score_counts(
+            deps.storage,
+            current.as_ref().map(|entry| entry.score),
+            Some(ranked_score),
+        )?;
+    }
+
+    // UPDATE SEASON
+
+    let season_id = active_season(deps.storage, env.block.time)?;
+    if let Some(season_id) = season_id {
+        update_season_rank(deps.storage, config.rank_policy, season_id, &player, score)?;
+    }
+
+    // UPDATE TOURNAMENTS
+
+    update_tournament_ranks(deps.storage, env, config.rank_policy, &player, score)?;
 
     // SAVE GAME
 
-    GAMES.save(deps.storage, player.clone(), &Game { score, game_time })?;
+    GAMES.save(
+        deps.storage,
+        (player.clone(), record_id),
+        &Game {
+            score,
+            game_time,
+            recorded_at: env.block.time,
+        },
+    )?;
+    GAME_PLAYERS.save(deps.storage, record_id, &player)?;
 
-    Ok(Response::new()
+    // UPDATE PLAYER STATS
+
+    let stats = match PLAYER_STATS.may_load(deps.storage, player.clone())? {
+        Some(stats) => PlayerStats {
+            games_played: stats.games_played + 1,
+            best_score: stats.best_score.max(score),
+            last_score: score,
+            total_score: stats.total_score.checked_add(score.into())?,
+            total_game_time: stats.total_game_time.checked_add(game_time.into())?,
+            first_played: stats.first_played,
+            last_played: env.block.time,
+        },
+        None => PlayerStats {
+            games_played: 1,
+            best_score: score,
+            last_score: score,
+            total_score: score.into(),
+            total_game_time: game_time.into(),
+            first_played: env.block.time,
+            last_played: env.block.time,
+        },
+    };
+    PLAYER_STATS.save(deps.storage, player.clone(), &stats)?;
+
+    // UPDATE STREAK
+
+    let streak = update_streak(deps.storage, env, &player)?;
+
+    // UPDATE CLUB
+
+    if let Some(club_id) = PLAYER_CLUBS.may_load(deps.storage, player.clone())? {
+        update_club_member(deps.storage, club_id, &player, Some(stats.best_score))?;
+        if let Some(season_id) = season_id {
+            update_season_club(
+                deps.storage,
+                config.rank_policy,
+                season_id,
+                club_id,
+                &player,
+                score,
+            )?;
+        }
+    }
+
+    // AWARD ACHIEVEMENTS
+
+    let minted = match &config.achievement_nft {
+        Some(nft) => award_achievements(deps.storage, env, nft, &player, |rule| match rule {
+            AchievementRule::FirstGame => true,
+            AchievementRule::ScoreAtLeast { score: min } => score >= *min,
+            AchievementRule::GamesPlayed { games } => stats.games_played >= *games,
+            AchievementRule::SeasonPodium { .. } => false,
+            AchievementRule::Streak { days } => streak.current >= *days,
+        })?,
+        None => vec![],
+    };
+
+    // EMIT EARNINGS
+
+    let earned = match &config.emission {
+        Some(emission) => {
+            let multiplier = config.streak.multiplier(streak.current);
+            emit_earnings(
+                deps.storage,
+                env,
+                emission,
+                &player,
+                score,
+                multiplier,
+                config.referral_share,
+            )?
+        }
+        None => Uint128::zero(),
+    };
+
+    let mut res = Response::new()
+        .add_submessages(minted)
         .add_attribute("action", "new_game")
+        .add_attribute("record_id", record_id.to_string())
         .add_attribute("player", player.to_string())
         .add_attribute("score", score.to_string())
-        .add_attribute("game_time", game_time.to_string()))
+        .add_attribute("game_time", game_time.to_string())
+        .add_attribute("streak", streak.current.to_string());
+    if let Some(season_id) = season_id {
+        res = res.add_attribute("season_id", season_id.to_string());
+    }
+    if !earned.is_zero() {
+        res = res.add_attribute("earned", earned);
+    }
+    Ok(res)
+}
+
+// Extends the player's streak on the first game of a day. Days without games
+// use up freezes, the streak starts over when there are not enough of them
+fn update_streak(
+    storage: &mut dyn Storage,
+    env: &Env,
+    player: &Addr,
+) -> Result<Streak, ContractError> {
+    let day = EmissionSchedule::day(env.block.time);
+    let streak = match PLAYER_STREAKS.may_load(storage, player.clone())? {
+        Some(streak) if streak.last_day >= day => return Ok(streak),
+        Some(streak) if streak.is_alive(day) => {
+            let missed = (day - streak.last_day - 1) as u32;
+            Streak {
+                current: streak.current + 1,
+                longest: streak.longest.max(streak.current + 1),
+                last_day: day,
+                freezes: streak.freezes - missed,
+            }
+        }
+        Some(streak) => Streak {
+            current: 1,
+            longest: streak.longest,
+            last_day: day,
+            freezes: streak.freezes,
+        },
+        None => Streak {
+            current: 1,
+            longest: 1,
+            last_day: day,
+            freezes: 0,
+        },
+    };
+    PLAYER_STREAKS.save(storage, player.clone(), &streak)?;
+    Ok(streak)
+}
+
+// Credits the tokens a game earns, times the streak multiplier, within what is
+// left today of the player's cap and of the global budget. They are minted when
+// the player claims them. The referrer's share comes on top, out of the budget
+fn emit_earnings(
+    storage: &mut dyn Storage,
+    env: &Env,
+    emission: &EmissionSchedule,
+    player: &Addr,
+    score: u64,
+    multiplier: Decimal,
+    referral_share: Decimal,
+) -> Result<Uint128, ContractError> {
+    let now = env.block.time;
+    if now < emission.start {
+        return Ok(Uint128::zero());
+    }
+
+    let day = EmissionSchedule::day(now);
+    let today = |entry: Option<DailyEmission>| {
+        entry
+            .filter(|entry| entry.day == day)
+            .unwrap_or(DailyEmission {
+                day,
+                amount: Uint128::zero(),
+            })
+    };
+    let mut emitted = today(EMISSION_TODAY.may_load(storage)?);
+    let mut player_emitted = today(PLAYER_EMISSION.may_load(storage, player.clone())?);
+    let budget = emission.daily_budget_at(now);
+
+    let earned = (Uint128::from(score) * emission.tokens_per_point * multiplier)
+        .min(
+            emission
+                .player_daily_cap
+                .saturating_sub(player_emitted.amount),
+        )
+        .min(budget.saturating_sub(emitted.amount));
+    if earned.is_zero() {
+        return Ok(earned);
+    }
+
+    emitted.amount += earned;
+    player_emitted.amount += earned;
+    PLAYER_EMISSION.save(storage, player.clone(), &player_emitted)?;
+    credit_token_rewards(
+        storage,
+        env,
+        player,
+        TokenRewards {
+            transfer: Uint128::zero(),
+            mint: earned,
+        },
+    )?;
+
+    if let Some(referrer) = REFERRERS.may_load(storage, player.clone())? {
+        let bonus = (earned * referral_share).min(budget.saturating_sub(emitted.amount));
+        if !bonus.is_zero() {
+            emitted.amount += bonus;
+            REFERRALS.update(
+                storage,
+                (referrer.clone(), player.clone()),
+                |earnings| -> StdResult<_> { Ok(earnings.unwrap_or_default() + bonus) },
+            )?;
+            REFERRAL_EARNINGS.update(storage, referrer.clone(), |earnings| -> StdResult<_> {
+                Ok(earnings.unwrap_or_default() + bonus)
+            })?;
+            credit_token_rewards(
+                storage,
+                env,
+                &referrer,
+                TokenRewards {
+                    transfer: Uint128::zero(),
+                    mint: bonus,
+                },
+            )?;
+        }
+    }
+    EMISSION_TODAY.save(storage, &emitted)?;
+    Ok(earned)
+}
+
+// Mints the achievements whose rule the player meets and does not have yet.
+// Token ids are unique per achievement and player. Mints reply on error, so
+// a failing NFT contract does not revert the game that earned them
+fn award_achievements(
+    storage: &mut dyn Storage,
+    env: &Env,
+    nft: &Addr,
+    player: &Addr,
+    earned: impl Fn(&AchievementRule) -> bool,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let achievements = ACHIEVEMENTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut msgs = vec![];
+    for (achievement_id, achievement) in achievements {
+        let key = (player.clone(), achievement_id);
+        if !earned(&achievement.rule) || PLAYER_ACHIEVEMENTS.has(storage, key.clone()) {
+            continue;
+        }
+
+        let token_id = format!("{}:{}", achievement_id, player);
+        PLAYER_ACHIEVEMENTS.save(
+            storage,
+            key,
+            &PlayerAchievement {
+                token_id: token_id.clone(),
+                earned_at: env.block.time,
+            },
+        )?;
+        let mint = Cw721ExecuteMsg::<AchievementMetadata, Empty>::Mint {
+            token_id,
+            owner: player.to_string(),
+            token_uri: None,
+            extension: AchievementMetadata {
+                name: Some(achievement.name),
+                description: Some(achievement.description),
+                attributes: Some(vec![
+                    Trait {
+                        display_type: None,
+                        trait_type: "achievement_id".to_string(),
+                        value: achievement_id.to_string(),
+                    },
+                    Trait {
+                        display_type: Some("date".to_string()),
+                        trait_type: "earned_at".to_string(),
+                        value: env.block.time.seconds().to_string(),
+                    },
+                ]),
+            },
+        };
+        let mint = WasmMsg::Execute {
+            contract_addr: nft.to_string(),
+            msg: to_json_binary(&mint)?,
+            funds: vec![],
+        };
+        msgs.push(SubMsg::reply_on_error(mint, MINT_ACHIEVEMENT_REPLY_ID));
+    }
+    Ok(msgs)
+}
+
+// Scores count for the running tournaments the player joined, as long as they
+// got enough players to take place
+fn update_tournament_ranks(
+    storage: &mut dyn Storage,
+    env: &Env,
+    policy: RankPolicy,
+    player: &Addr,
+    score: u64,
+) -> Result<(), ContractError> {
+    let now = env.block.time;
+    let joined = PLAYER_TOURNAMENTS
+        .sub_prefix(player.clone())
+        .keys(
+            storage,
+            Some(Bound::exclusive((now.seconds(), u64::MAX))),
+            None,
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (_, tournament_id) in joined {
+        let tournament = TOURNAMENTS.load(storage, tournament_id)?;
+        if now < tournament.start
+            || tournament.state != TournamentState::Open
+            || tournament.under_subscribed()
+        {
+            continue;
+        }
+
+        let key = (tournament_id, player.clone());
+        let current = tournament_rank().may_load(storage, key.clone())?;
+        let tournament_score =
+            ranked_score(policy, current.as_ref().map(|entry| entry.score), score)?;
+        if current.as_ref().map(|entry| entry.score) != Some(tournament_score) {
+            tournament_rank().replace(
+                storage,
+                key,
+                Some(&TournamentRankEntry {
+                    tournament_id,
+                    score: tournament_score,
+                }),
+                current.as_ref(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn update_season_rank(
+    storage: &mut dyn Storage,
+    policy: RankPolicy,
+    season_id: u64,
+    player: &Addr,
+    score: u64,
+) -> Result<(), ContractError> {
+    SEASON_TOTALS.update(storage, season_id, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + 1)
+    })?;
+
+    let key = (season_id, player.clone());
+    let current = season_rank().may_load(storage, key.clone())?;
+    let season_score = ranked_score(policy, current.as_ref().map(|entry| entry.score), score)?;
+    if current.as_ref().map(|entry| entry.score) != Some(season_score) {
+        season_rank().replace(
+            storage,
+            key,
+            Some(&SeasonRankEntry {
+                season_id,
+                score: season_score,
+            }),
+            current.as_ref(),
+        )?;
+    }
+    Ok(())
+}
+
+// Each game counts for the club the player is in when playing it, so a club
+// gets nothing from the season scores its members earned elsewhere
+fn update_season_club(
+    storage: &mut dyn Storage,
+    policy: RankPolicy,
+    season_id: u64,
+    club_id: u64,
+    player: &Addr,
+    score: u64,
+) -> Result<(), ContractError> {
+    let key = (season_id, club_id, player.clone());
+    let current = season_club_members().may_load(storage, key.clone())?;
+    let member_score = ranked_score(policy, current.as_ref().map(|member| member.score), score)?;
+    if current.as_ref().map(|member| member.score) == Some(member_score) {
+        return Ok(());
+    }
+    season_club_members().replace(
+        storage,
+        key,
+        Some(&SeasonClubMember {
+            season_id,
+            club_id,
+            score: member_score,
+        }),
+        current.as_ref(),
+    )?;
+
+    let club_key = (season_id, club_id);
+    let club_score = season_club_scores().may_load(storage, club_key)?;
+    let score = match clubs().load(storage, club_id)?.scoring {
+        ClubScoring::Sum => club_score
+            .as_ref()
+            .map_or(0, |club| club.score)
+            .saturating_sub(current.map_or(0, |member| member.score))
+            .saturating_add(member_score),
+        ClubScoring::TopK { k } => season_club_members()
+            .idx
+            .score
+            .sub_prefix((season_id, club_id))
+            .range(storage, None, None, Order::Descending)
+            .take(k as usize)
+            .try_fold(0u64, |total, item| {
+                item.map(|(_, member)| total.saturating_add(member.score))
+            })?,
+    };
+    season_club_scores().replace(
+        storage,
+        club_key,
+        Some(&SeasonClubScore { season_id, score }),
+        club_score.as_ref(),
+    )?;
+    Ok(())
+}
+
+pub fn add_submitter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    game_id: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+
+    let submitter = deps.api.addr_validate(&address)?;
+    let game_id = registered_game(game_id);
+    match &game_id {
+        Some(game_id) => {
+            load_registered_game(deps.storage, game_id)?;
+            GAME_SUBMITTERS.save(deps.storage, (game_id, submitter.clone()), &Empty {})?;
+        }
+        None => SUBMITTERS.save(deps.storage, submitter.clone(), &Empty {})?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_submitter")
+        .add_attribute("submitter", submitter)
+        .add_attribute("game", game_id.as_deref().unwrap_or(DEFAULT_GAME_ID)))
+}
+
+pub fn remove_submitter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    game_id: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+
+    let submitter = deps.api.addr_validate(&address)?;
+    let game_id = registered_game(game_id);
+    match &game_id {
+        Some(game_id) => GAME_SUBMITTERS.remove(deps.storage, (game_id, submitter.clone())),
+        None => SUBMITTERS.remove(deps.storage, submitter.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_submitter")
+        .add_attribute("submitter", submitter)
+        .add_attribute("game", game_id.as_deref().unwrap_or(DEFAULT_GAME_ID)))
+}
+
+pub fn set_server_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    server_key: Option<ServerKey>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.server_key = server_key.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_server_key")
+        .add_attribute("enabled", server_key.is_some().to_string()))
+}
+
+pub fn set_session_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    session_limits: SessionLimits,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    validate_session_limits(&session_limits)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.session_limits = session_limits.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_session_limits")
+        .add_attribute("min_duration", session_limits.min_duration.to_string())
+        .add_attribute("max_duration", session_limits.max_duration.to_string())
+        .add_attribute("sessions_only", session_limits.sessions_only.to_string()))
+}
+
+pub fn validate_session_limits(limits: &SessionLimits) -> Result<(), ContractError> {
+    if limits.min_duration > limits.max_duration {
+        return Err(ContractError::InvalidSessionLimits {});
+    }
+    Ok(())
+}
+
+pub fn set_game_rules(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_rules: GameRules,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    validate_game_rules(&game_rules)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.game_rules = game_rules;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_game_rules"))
+}
+
+pub fn validate_game_rules(rules: &GameRules) -> Result<(), ContractError> {
+    if let (Some(min), Some(max)) = (rules.min_game_time, rules.max_game_time) {
+        if min > max {
+            return Err(ContractError::InvalidGameRules {});
+        }
+    }
+    Ok(())
+}
+
+pub fn create_season(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    start: Timestamp,
+    end: Timestamp,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    if start >= end || start < env.block.time {
+        return Err(ContractError::InvalidSeason {});
+    }
+
+    // Seasons follow each other, which keeps at most one of them active
+    let last = SEASON_COUNT.load(deps.storage)?;
+    if last > 0 && start < SEASONS.load(deps.storage, last)?.end {
+        return Err(ContractError::SeasonOverlap { season_id: last });
+    }
+
+    let season_id = last + 1;
+    SEASON_COUNT.save(deps.storage, &season_id)?;
+    SEASONS.save(
+        deps.storage,
+        season_id,
+        &Season {
+            name,
+            start,
+            end,
+            finalized: false,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_season")
+        .add_attribute("season_id", season_id.to_string()))
+}
+
+pub fn fund_season(
+    deps: DepsMut,
+    info: MessageInfo,
+    season_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+    if SEASONS.load(deps.storage, season_id)?.finalized {
+        return Err(ContractError::SeasonAlreadyFinalized { season_id });
+    }
+
+    let mut pool = SEASON_POOLS
+        .may_load(deps.storage, season_id)?
+        .unwrap_or_default();
+    add_coins(&mut pool, info.funds)?;
+    SEASON_POOLS.save(deps.storage, season_id, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_season")
+        .add_attribute("season_id", season_id.to_string()))
+}
+
+pub fn fund_season_club_prize(
+    deps: DepsMut,
+    info: MessageInfo,
+    season_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+    if SEASONS.load(deps.storage, season_id)?.finalized {
+        return Err(ContractError::SeasonAlreadyFinalized { season_id });
+    }
+
+    let mut pool = SEASON_CLUB_POOLS
+        .may_load(deps.storage, season_id)?
+        .unwrap_or_default();
+    add_coins(&mut pool, info.funds)?;
+    SEASON_CLUB_POOLS.save(deps.storage, season_id, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_season_club_prize")
+        .add_attribute("season_id", season_id.to_string()))
+}
+
+pub fn finalize_season(deps: DepsMut, env: Env, season_id: u64) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let mut season = SEASONS.load(deps.storage, season_id)?;
+    if season.finalized {
+        return Err(ContractError::SeasonAlreadyFinalized { season_id });
+    }
+    if season.status(env.block.time) != SeasonStatus::Finished {
+        return Err(ContractError::SeasonNotFinished { season_id });
+    }
+
+    // Ties are settled the same way GetRank orders them
+    let config = CONFIG.load(deps.storage)?;
+    let payout_table = config.payout_table;
+    let winners = season_rank()
+        .idx
+        .score
+        .sub_prefix(season_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .take(payout_table.len())
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Shares are taken from the funded pools and credited to the winners, who
+    // claim them later. What rounding or missing players leave unpaid stays
+    // in the pools
+    let funded = SEASON_POOLS
+        .may_load(deps.storage, season_id)?
+        .unwrap_or_default();
+    let mut pool = funded.clone();
+    let funded_tokens = SEASON_TOKEN_POOLS
+        .may_load(deps.storage, season_id)?
+        .unwrap_or_default();
+    let mut token_pool = funded_tokens;
+    for (index, ((_, player), share)) in winners.into_iter().zip(payout_table).enumerate() {
+        let amount = funded
+            .iter()
+            .map(|coin| Coin::new((coin.amount * share).u128(), &coin.denom))
+            .filter(|coin| !coin.amount.is_zero())
+            .collect::<Vec<_>>();
+        for paid in &amount {
+            if let Some(coin) = pool.iter_mut().find(|coin| coin.denom == paid.denom) {
+                coin.amount = coin.amount.checked_sub(paid.amount)?;
+            }
+        }
+        let token_amount = funded_tokens * share;
+        token_pool = token_pool.checked_sub(token_amount)?;
+
+        let position = index as u64 + 1;
+        SEASON_PAYOUTS.save(
+            deps.storage,
+            (season_id, position),
+            &SeasonPayout {
+                player: player.clone(),
+                amount: amount.clone(),
+                token_amount,
+            },
+        )?;
+        credit_rewards(deps.storage, &env, &player, amount)?;
+        credit_token_rewards(
+            deps.storage,
+            &env,
+            &player,
+            TokenRewards {
+                transfer: token_amount,
+                mint: Uint128::zero(),
+            },
+        )?;
+    }
+
+    pool.retain(|coin| !coin.amount.is_zero());
+    SEASON_POOLS.save(deps.storage, season_id, &pool)?;
+    SEASON_TOKEN_POOLS.save(deps.storage, season_id, &token_pool)?;
+    season.finalized = true;
+    SEASONS.save(deps.storage, season_id, &season)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "finalize_season")
+        .add_attribute("season_id", season_id.to_string());
+
+    if let Some(nft) = &config.achievement_nft {
+        let podium = season_rank()
+            .idx
+            .score
+            .sub_prefix(season_id)
+            .keys(deps.storage, None, None, Order::Descending)
+            .take(MAX_PODIUM as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (position, (_, player)) in podium.into_iter().enumerate() {
+            let minted = award_achievements(
+                deps.storage,
+                &env,
+                nft,
+                &player,
+                |rule| matches!(rule, AchievementRule::SeasonPodium { top } if position < *top as usize),
+            )?;
+            res = res.add_submessages(minted);
+        }
+    }
+
+    // The club prize goes to the club that scored the most in the season, and
+    // stays in the season when no club member played it. Clubs removed since
+    // then are passed over
+    let club_pool = SEASON_CLUB_POOLS
+        .may_load(deps.storage, season_id)?
+        .unwrap_or_default();
+    let mut top_club = None;
+    if !club_pool.is_empty() {
+        let season_clubs = season_club_scores().idx.score.sub_prefix(season_id).keys(
+            deps.storage,
+            None,
+            None,
+            Order::Descending,
+        );
+        for key in season_clubs {
+            let (_, club_id) = key?;
+            if clubs().has(deps.storage, club_id) {
+                top_club = Some(club_id);
+                break;
+            }
+        }
+    }
+    if let Some(club_id) = top_club {
+        let mut treasury = CLUB_TREASURIES
+            .may_load(deps.storage, club_id)?
+            .unwrap_or_default();
+        add_coins(&mut treasury, club_pool)?;
+        CLUB_TREASURIES.save(deps.storage, club_id, &treasury)?;
+        SEASON_CLUB_POOLS.remove(deps.storage, season_id);
+        SEASON_CLUB_WINNERS.save(deps.storage, season_id, &club_id)?;
+        res = res.add_attribute("club_id", club_id.to_string());
+    }
+    Ok(res)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_tournament(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entry_fee: Coin,
+    start: Timestamp,
+    end: Timestamp,
+    max_players: u32,
+    min_players: Option<u32>,
+    payout_table: Vec<Decimal>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    validate_payout_table(&payout_table)?;
+
+    let min_players = min_players.unwrap_or(payout_table.len() as u32).max(1);
+    if start >= end
+        || start < env.block.time
+        || entry_fee.amount.is_zero()
+        || min_players > max_players
+    {
+        return Err(ContractError::InvalidTournament {});
+    }
+
+    let house_fee = CONFIG.load(deps.storage)?.house_fee;
+    let tournament_id = TOURNAMENT_COUNT.load(deps.storage)? + 1;
+    TOURNAMENT_COUNT.save(deps.storage, &tournament_id)?;
+    TOURNAMENTS.save(
+        deps.storage,
+        tournament_id,
+        &Tournament {
+            entry_fee,
+            start,
+            end,
+            max_players,
+            min_players,
+            payout_table,
+            house_fee,
+            players: 0,
+            state: TournamentState::Open,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_tournament")
+        .add_attribute("tournament_id", tournament_id.to_string()))
+}
+
+pub fn join_tournament(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tournament_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let mut tournament = TOURNAMENTS.load(deps.storage, tournament_id)?;
+    if tournament.state != TournamentState::Open || env.block.time >= tournament.start {
+        return Err(ContractError::TournamentClosed { tournament_id });
+    }
+    if tournament.players >= tournament.max_players {
+        return Err(ContractError::TournamentFull { tournament_id });
+    }
+    if info.funds != [tournament.entry_fee.clone()] {
+        return Err(ContractError::WrongEntryFee {
+            entry_fee: tournament.entry_fee,
+        });
+    }
+
+    let player = info.sender;
+    if TOURNAMENT_PLAYERS.has(deps.storage, (tournament_id, player.clone())) {
+        return Err(ContractError::AlreadyJoined { tournament_id });
+    }
+    TOURNAMENT_PLAYERS.save(deps.storage, (tournament_id, player.clone()), &Empty {})?;
+    PLAYER_TOURNAMENTS.save(
+        deps.storage,
+        (player.clone(), tournament.end.seconds(), tournament_id),
+        &Empty {},
+    )?;
+    tournament.players += 1;
+    TOURNAMENTS.save(deps.storage, tournament_id, &tournament)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "join_tournament")
+        .add_attribute("tournament_id", tournament_id.to_string())
+        .add_attribute("player", player))
+}
+
+pub fn settle_tournament(
+    deps: DepsMut,
+    env: Env,
+    tournament_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let mut tournament = TOURNAMENTS.load(deps.storage, tournament_id)?;
+    if tournament.state != TournamentState::Open {
+        return Err(ContractError::TournamentClosed { tournament_id });
+    }
+    if env.block.time < tournament.end {
+        return Err(ContractError::TournamentNotFinished { tournament_id });
+    }
+
+    let winners = tournament_rank()
+        .idx
+        .score
+        .sub_prefix(tournament_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .take(tournament.payout_table.len())
+        .collect::<StdResult<Vec<_>>>()?;
+    if tournament.under_subscribed() || winners.is_empty() {
+        return Err(ContractError::TournamentNotSettleable { tournament_id });
+    }
+
+    let denom = tournament.entry_fee.denom.clone();
+    let pool = tournament
+        .entry_fee
+        .amount
+        .checked_mul(tournament.players.into())?;
+    let fee = pool * tournament.house_fee;
+    let prizes = pool.checked_sub(fee)?;
+
+    // What rounding or missing players leave unpaid goes to the first place
+    let mut amounts = winners
+        .iter()
+        .zip(&tournament.payout_table)
+        .map(|(_, share)| prizes * *share)
+        .collect::<Vec<_>>();
+    let paid = amounts
+        .iter()
+        .try_fold(Uint128::zero(), |total, amount| total.checked_add(*amount))?;
+    amounts[0] = amounts[0].checked_add(prizes.checked_sub(paid)?)?;
+
+    for ((_, player), amount) in winners.into_iter().zip(amounts) {
+        credit_rewards(deps.storage, &env, &player, coins(amount.u128(), &denom))?;
+    }
+
+    tournament.state = TournamentState::Settled;
+    TOURNAMENTS.save(deps.storage, tournament_id, &tournament)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "settle_tournament")
+        .add_attribute("tournament_id", tournament_id.to_string())
+        .add_attribute("house_fee", fee);
+    if !fee.is_zero() {
+        // A fee is only ever set along with a treasury
+        let treasury = CONFIG
+            .load(deps.storage)?
+            .treasury
+            .ok_or(ContractError::InvalidHouseFee {})?;
+        res = res.add_message(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: coins(fee.u128(), denom),
+        });
+    }
+    Ok(res)
+}
+
+pub fn cancel_tournament(
+    deps: DepsMut,
+    info: MessageInfo,
+    tournament_id: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Moderator)?;
+
+    let mut tournament = TOURNAMENTS.load(deps.storage, tournament_id)?;
+    if tournament.state != TournamentState::Open {
+        return Err(ContractError::TournamentClosed { tournament_id });
+    }
+    tournament.state = TournamentState::Cancelled;
+    TOURNAMENTS.save(deps.storage, tournament_id, &tournament)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_tournament")
+        .add_attribute("tournament_id", tournament_id.to_string()))
+}
+
+pub fn refund_tournament(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tournament_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let tournament = TOURNAMENTS.load(deps.storage, tournament_id)?;
+
+    // Entries are refunded when the tournament was cancelled, did not get
+    // enough players or ended without any score
+    let refundable = match tournament.state {
+        TournamentState::Cancelled => true,
+        TournamentState::Settled => false,
+        TournamentState::Open => {
+            (env.block.time >= tournament.start && tournament.under_subscribed())
+                || (env.block.time >= tournament.end
+                    && tournament_rank()
+                        .idx
+                        .score
+                        .sub_prefix(tournament_id)
+                        .keys_raw(deps.storage, None, None, Order::Ascending)
+                        .next()
+                        .is_none())
+        }
+    };
+    if !refundable {
+        return Err(ContractError::NotRefundable { tournament_id });
+    }
+
+    let player = info.sender;
+    if !TOURNAMENT_PLAYERS.has(deps.storage, (tournament_id, player.clone())) {
+        return Err(ContractError::NotJoined { tournament_id });
+    }
+    TOURNAMENT_PLAYERS.remove(deps.storage, (tournament_id, player.clone()));
+    PLAYER_TOURNAMENTS.remove(
+        deps.storage,
+        (player.clone(), tournament.end.seconds(), tournament_id),
+    );
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: player.to_string(),
+            amount: vec![tournament.entry_fee],
+        })
+        .add_attribute("action", "refund_tournament")
+        .add_attribute("tournament_id", tournament_id.to_string())
+        .add_attribute("player", player))
+}
+
+pub fn create_club(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    open: bool,
+    max_members: u32,
+    scoring: ClubScoring,
+) -> Result<Response, ContractError> {
+    let invalid_k = match scoring {
+        ClubScoring::Sum => false,
+        ClubScoring::TopK { k } => k == 0 || k > max_members,
+    };
+    if name.is_empty() || max_members == 0 || max_members > MAX_CLUB_MEMBERS || invalid_k {
+        return Err(ContractError::InvalidClub {});
+    }
+    let owner = info.sender;
+    if let Some(club_id) = PLAYER_CLUBS.may_load(deps.storage, owner.clone())? {
+        return Err(ContractError::AlreadyInClub { club_id });
+    }
+
+    let club_id = CLUB_COUNT.load(deps.storage)? + 1;
+    CLUB_COUNT.save(deps.storage, &club_id)?;
+    clubs().save(
+        deps.storage,
+        club_id,
+        &Club {
+            name,
+            owner: owner.clone(),
+            open,
+            max_members,
+            members: 0,
+            scoring,
+            score: 0,
+            split: ClubSplit::default(),
+        },
+    )?;
+    add_club_member(deps.storage, club_id, &owner)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_club")
+        .add_attribute("club_id", club_id.to_string())
+        .add_attribute("owner", owner))
+}
+
+pub fn invite_to_club(
+    deps: DepsMut,
+    info: MessageInfo,
+    club_id: u64,
+    player: String,
+) -> Result<Response, ContractError> {
+    let club = clubs().load(deps.storage, club_id)?;
+    if club.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let player = deps.api.addr_validate(&player)?;
+    CLUB_INVITES.save(deps.storage, (club_id, player.clone()), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "invite_to_club")
+        .add_attribute("club_id", club_id.to_string())
+        .add_attribute("player", player))
+}
+
+pub fn join_club(
+    deps: DepsMut,
+    info: MessageInfo,
+    club_id: u64,
+) -> Result<Response, ContractError> {
+    let club = clubs().load(deps.storage, club_id)?;
+    let player = info.sender;
+    if let Some(club_id) = PLAYER_CLUBS.may_load(deps.storage, player.clone())? {
+        return Err(ContractError::AlreadyInClub { club_id });
+    }
+    if club.members >= club.max_members {
+        return Err(ContractError::ClubFull { club_id });
+    }
+
+    // Invites are used up by joining
+    let invite = (club_id, player.clone());
+    if !club.open && !CLUB_INVITES.has(deps.storage, invite.clone()) {
+        return Err(ContractError::ClubInviteOnly { club_id });
+    }
+    CLUB_INVITES.remove(deps.storage, invite);
+    add_club_member(deps.storage, club_id, &player)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "join_club")
+        .add_attribute("club_id", club_id.to_string())
+        .add_attribute("player", player))
+}
+
+pub fn leave_club(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let player = info.sender;
+    let club_id = PLAYER_CLUBS
+        .may_load(deps.storage, player.clone())?
+        .ok_or(ContractError::NotInClub {})?;
+    let club = clubs().load(deps.storage, club_id)?;
+    if club.owner == player && club.members > 1 {
+        return Err(ContractError::ClubOwnerCannotLeave {});
+    }
+    if club.members == 1 && CLUB_TREASURIES.has(deps.storage, club_id) {
+        return Err(ContractError::ClubTreasuryNotEmpty { club_id });
+    }
+
+    PLAYER_CLUBS.remove(deps.storage, player.clone());
+    if club.members == 1 {
+        club_members().remove(deps.storage, (club_id, player.clone()))?;
+        clubs().remove(deps.storage, club_id)?;
+    } else {
+        update_club_member(deps.storage, club_id, &player, None)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "leave_club")
+        .add_attribute("club_id", club_id.to_string())
+        .add_attribute("player", player))
+}
+
+pub fn deposit_to_club(
+    deps: DepsMut,
+    info: MessageInfo,
+    club_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+    clubs().load(deps.storage, club_id)?;
+
+    let mut treasury = CLUB_TREASURIES
+        .may_load(deps.storage, club_id)?
+        .unwrap_or_default();
+    add_coins(&mut treasury, info.funds)?;
+    CLUB_TREASURIES.save(deps.storage, club_id, &treasury)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit_to_club")
+        .add_attribute("club_id", club_id.to_string()))
+}
+
+pub fn set_club_split(
+    deps: DepsMut,
+    info: MessageInfo,
+    club_id: u64,
+    split: ClubSplit,
+) -> Result<Response, ContractError> {
+    let mut club = clubs().load(deps.storage, club_id)?;
+    if club.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let ClubSplit::Weights { weights } = &split {
+        let mut players = weights.iter().map(|(player, _)| player).collect::<Vec<_>>();
+        players.sort();
+        players.dedup();
+        let valid = !weights.is_empty()
+            && players.len() == weights.len()
+            && weights.iter().all(|(_, weight)| *weight > 0);
+        if !valid {
+            return Err(ContractError::InvalidClubSplit {});
+        }
+        for player in players {
+            if !club_members().has(deps.storage, (club_id, player.clone())) {
+                return Err(ContractError::InvalidClubSplit {});
+            }
+        }
+    }
+    club.split = split;
+    clubs().save(deps.storage, club_id, &club)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_club_split")
+        .add_attribute("club_id", club_id.to_string()))
+}
+
+pub fn distribute_club_treasury(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    club_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let club = clubs().load(deps.storage, club_id)?;
+    if club.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut treasury = CLUB_TREASURIES
+        .may_load(deps.storage, club_id)?
+        .ok_or(ContractError::NoFunds {})?;
+
+    // Members are bounded by MAX_CLUB_MEMBERS. Weights of players who left the
+    // club are skipped
+    let members = club_members()
+        .prefix(club_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let weights = match club.split {
+        ClubSplit::Contribution => members
+            .into_iter()
+            .map(|(player, member)| (player, Uint128::from(member.score)))
+            .collect::<Vec<_>>(),
+        ClubSplit::Weights { weights } => weights
+            .into_iter()
+            .filter(|(player, _)| members.iter().any(|(member, _)| member == player))
+            .map(|(player, weight)| (player, Uint128::from(weight)))
+            .collect(),
+    };
+    let total = weights
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, weight)| {
+            total.checked_add(*weight)
+        })?;
+    if total.is_zero() {
+        return Err(ContractError::InvalidClubSplit {});
+    }
+
+    // What rounding leaves undistributed stays in the treasury
+    let funded = treasury.clone();
+    let mut shares = vec![];
+    for (player, weight) in weights {
+        let amount = funded
+            .iter()
+            .map(|coin| {
+                Coin::new(
+                    coin.amount.multiply_ratio(weight, total).u128(),
+                    &coin.denom,
+                )
+            })
+            .filter(|coin| !coin.amount.is_zero())
+            .collect::<Vec<_>>();
+        if amount.is_empty() {
+            continue;
+        }
+        for paid in &amount {
+            if let Some(coin) = treasury.iter_mut().find(|coin| coin.denom == paid.denom) {
+                coin.amount = coin.amount.checked_sub(paid.amount)?;
+            }
+        }
+        credit_rewards(deps.storage, &env, &player, amount.clone())?;
+        shares.push((player, amount));
+    }
+
+    treasury.retain(|coin| !coin.amount.is_zero());
+    if treasury.is_empty() {
+        CLUB_TREASURIES.remove(deps.storage, club_id);
+    } else {
+        CLUB_TREASURIES.save(deps.storage, club_id, &treasury)?;
+    }
+    let payout_id = CLUB_PAYOUTS
+        .prefix(club_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default()
+        + 1;
+    CLUB_PAYOUTS.save(
+        deps.storage,
+        (club_id, payout_id),
+        &ClubPayout {
+            distributed_at: env.block.time,
+            shares,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "distribute_club_treasury")
+        .add_attribute("club_id", club_id.to_string())
+        .add_attribute("payout_id", payout_id.to_string()))
+}
+
+// New members bring the best score they already made
+fn add_club_member(
+    storage: &mut dyn Storage,
+    club_id: u64,
+    player: &Addr,
+) -> Result<(), ContractError> {
+    PLAYER_CLUBS.save(storage, player.clone(), &club_id)?;
+    let best_score = PLAYER_STATS
+        .may_load(storage, player.clone())?
+        .map_or(0, |stats| stats.best_score);
+    update_club_member(storage, club_id, player, Some(best_score))
+}
+
+// Sets the best score a member brings to the club, `None` removing the member,
+// and moves the club on the club leaderboard
+fn update_club_member(
+    storage: &mut dyn Storage,
+    club_id: u64,
+    player: &Addr,
+    score: Option<u64>,
+) -> Result<(), ContractError> {
+    let key = (club_id, player.clone());
+    let current = club_members().may_load(storage, key.clone())?;
+    let member = score.map(|score| ClubMember { club_id, score });
+    if current == member {
+        return Ok(());
+    }
+    club_members().replace(storage, key, member.as_ref(), current.as_ref())?;
+
+    let mut club = clubs().load(storage, club_id)?;
+    match (&current, &member) {
+        (None, Some(_)) => club.members += 1,
+        (Some(_), None) => club.members -= 1,
+        _ => {}
+    }
+    club.score = match club.scoring {
+        ClubScoring::Sum => club
+            .score
+            .saturating_sub(current.map_or(0, |member| member.score))
+            .saturating_add(member.map_or(0, |member| member.score)),
+        ClubScoring::TopK { k } => club_members()
+            .idx
+            .score
+            .sub_prefix(club_id)
+            .range(storage, None, None, Order::Descending)
+            .take(k as usize)
+            .try_fold(0u64, |total, item| {
+                item.map(|(_, member)| total.saturating_add(member.score))
+            })?,
+    };
+    clubs().save(storage, club_id, &club)?;
+    Ok(())
+}
+
+pub fn set_house_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    house_fee: Decimal,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    let treasury = treasury
+        .map(|treasury| deps.api.addr_validate(&treasury))
+        .transpose()?;
+    validate_house_fee(house_fee, treasury.as_ref())?;
+
+    // Tournaments already created keep their fee
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.house_fee = house_fee;
+        config.treasury = treasury;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_house_fee"))
+}
+
+pub fn validate_house_fee(
+    house_fee: Decimal,
+    treasury: Option<&Addr>,
+) -> Result<(), ContractError> {
+    if house_fee > Decimal::one() || (!house_fee.is_zero() && treasury.is_none()) {
+        return Err(ContractError::InvalidHouseFee {});
+    }
+    Ok(())
+}
+
+// Adds `amount` to the rewards the player can claim, restarting the claim period
+pub fn credit_rewards(
+    storage: &mut dyn Storage,
+    env: &Env,
+    player: &Addr,
+    amount: Vec<Coin>,
+) -> Result<(), ContractError> {
+    if amount.is_empty() {
+        return Ok(());
+    }
+
+    let mut pending = PENDING_REWARDS
+        .may_load(storage, player.clone())?
+        .unwrap_or_default();
+    add_coins(&mut pending, amount)?;
+    PENDING_REWARDS.save(storage, player.clone(), &pending)?;
+    REWARDS_CREDITED_AT.save(storage, player.clone(), &env.block.time)?;
+    Ok(())
+}
+
+// Same as `credit_rewards` for the CW20 reward token
+pub fn credit_token_rewards(
+    storage: &mut dyn Storage,
+    env: &Env,
+    player: &Addr,
+    tokens: TokenRewards,
+) -> Result<(), ContractError> {
+    if tokens.transfer.is_zero() && tokens.mint.is_zero() {
+        return Ok(());
+    }
+
+    let pending = PENDING_TOKEN_REWARDS
+        .may_load(storage, player.clone())?
+        .unwrap_or_default();
+    PENDING_TOKEN_REWARDS.save(
+        storage,
+        player.clone(),
+        &TokenRewards {
+            transfer: pending.transfer.checked_add(tokens.transfer)?,
+            mint: pending.mint.checked_add(tokens.mint)?,
+        },
+    )?;
+    REWARDS_CREDITED_AT.save(storage, player.clone(), &env.block.time)?;
+    Ok(())
+}
+
+fn add_coins(total: &mut Vec<Coin>, coins: Vec<Coin>) -> Result<(), ContractError> {
+    for added in coins {
+        match total.iter_mut().find(|coin| coin.denom == added.denom) {
+            Some(coin) => coin.amount = coin.amount.checked_add(added.amount)?,
+            None => total.push(added),
+        }
+    }
+    Ok(())
+}
+
+fn token_msg(token: &Addr, msg: Cw20ExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+pub fn claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let player = info.sender;
+    let amount = PENDING_REWARDS.may_load(deps.storage, player.clone())?;
+    let tokens = PENDING_TOKEN_REWARDS.may_load(deps.storage, player.clone())?;
+    if amount.is_none() && tokens.is_none() {
+        return Err(ContractError::NoPendingRewards {});
+    }
+    PENDING_REWARDS.remove(deps.storage, player.clone());
+    PENDING_TOKEN_REWARDS.remove(deps.storage, player.clone());
+    REWARDS_CREDITED_AT.remove(deps.storage, player.clone());
+
+    let mut res = Response::new()
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("player", player.clone());
+    if let Some(amount) = amount {
+        res = res.add_message(BankMsg::Send {
+            to_address: player.to_string(),
+            amount,
+        });
+    }
+    if let Some(tokens) = tokens {
+        let token = CONFIG
+            .load(deps.storage)?
+            .reward_token
+            .ok_or(ContractError::RewardTokenNotSet {})?;
+        // Prizes are held by the contract while earnings are minted on claim
+        if !tokens.transfer.is_zero() {
+            let msg = Cw20ExecuteMsg::Transfer {
+                recipient: player.to_string(),
+                amount: tokens.transfer,
+            };
+            res = res.add_message(token_msg(&token, msg)?);
+        }
+        if !tokens.mint.is_zero() {
+            let msg = Cw20ExecuteMsg::Mint {
+                recipient: player.to_string(),
+                amount: tokens.mint,
+            };
+            res = res.add_message(token_msg(&token, msg)?);
+        }
+    }
+    Ok(res)
+}
+
+pub fn sweep_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    players: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    assert_not_paused(deps.as_ref())?;
+    let config = CONFIG.load(deps.storage)?;
+
+    // Rewards left unclaimed for the whole claim period go to the treasury,
+    // or to the owner when there is none, never to the treasurer sweeping.
+    // Tokens that would have been minted are simply dropped
+    let recipient = match config.treasury.clone() {
+        Some(treasury) => treasury,
+        None => OWNERSHIP
+            .load(deps.storage)?
+            .owner
+            .ok_or(ContractError::NoSweepRecipient {})?,
+    };
+    let mut swept = vec![];
+    let mut swept_tokens = Uint128::zero();
+    for player in players {
+        let player = deps.api.addr_validate(&player)?;
+        let credited_at = REWARDS_CREDITED_AT.load(deps.storage, player.clone())?;
+        if env.block.time < credited_at.plus_seconds(config.claim_period) {
+            return Err(ContractError::RewardsNotExpired {
+                player: player.to_string(),
+            });
+        }
+
+        if let Some(amount) = PENDING_REWARDS.may_load(deps.storage, player.clone())? {
+            add_coins(&mut swept, amount)?;
+        }
+        if let Some(tokens) = PENDING_TOKEN_REWARDS.may_load(deps.storage, player.clone())? {
+            swept_tokens = swept_tokens.checked_add(tokens.transfer)?;
+        }
+        PENDING_REWARDS.remove(deps.storage, player.clone());
+        PENDING_TOKEN_REWARDS.remove(deps.storage, player.clone());
+        REWARDS_CREDITED_AT.remove(deps.storage, player);
+    }
+
+    let mut res = Response::new().add_attribute("action", "sweep_rewards");
+    if !swept.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: swept,
+        });
+    }
+    if !swept_tokens.is_zero() {
+        let token = config
+            .reward_token
+            .ok_or(ContractError::RewardTokenNotSet {})?;
+        let msg = Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: swept_tokens,
+        };
+        res = res.add_message(token_msg(&token, msg)?);
+    }
+    Ok(res)
+}
+
+pub fn set_achievement_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    let nft = nft.map(|nft| deps.api.addr_validate(&nft)).transpose()?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.achievement_nft = nft.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_achievement_nft")
+        .add_attribute("enabled", nft.is_some().to_string()))
+}
+
+pub fn add_achievement(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    description: String,
+    rule: AchievementRule,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Moderator)?;
+    let valid_rule = match rule {
+        AchievementRule::FirstGame => true,
+        AchievementRule::ScoreAtLeast { score } => score > 0,
+        AchievementRule::GamesPlayed { games } => games > 0,
+        AchievementRule::SeasonPodium { top } => top > 0 && top <= MAX_PODIUM,
+        AchievementRule::Streak { days } => days > 0,
+    };
+    let achievements = ACHIEVEMENTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if name.is_empty() || !valid_rule || achievements >= MAX_ACHIEVEMENTS {
+        return Err(ContractError::InvalidAchievement {});
+    }
+
+    let achievement_id = ACHIEVEMENT_COUNT.load(deps.storage)? + 1;
+    ACHIEVEMENT_COUNT.save(deps.storage, &achievement_id)?;
+    ACHIEVEMENTS.save(
+        deps.storage,
+        achievement_id,
+        &Achievement {
+            name,
+            description,
+            rule,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_achievement")
+        .add_attribute("achievement_id", achievement_id.to_string()))
+}
+
+pub fn remove_achievement(
+    deps: DepsMut,
+    info: MessageInfo,
+    achievement_id: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Moderator)?;
+    ACHIEVEMENTS.load(deps.storage, achievement_id)?;
+    ACHIEVEMENTS.remove(deps.storage, achievement_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_achievement")
+        .add_attribute("achievement_id", achievement_id.to_string()))
+}
+
+pub fn set_streak_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    streak: StreakConfig,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        validate_streak_config(&streak, config.treasury.as_ref())?;
+        config.streak = streak;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_streak_config"))
+}
+
+pub fn validate_streak_config(
+    streak: &StreakConfig,
+    treasury: Option<&Addr>,
+) -> Result<(), ContractError> {
+    let increasing = streak
+        .multipliers
+        .windows(2)
+        .all(|pair| pair[0].0 < pair[1].0);
+    let valid_multipliers = streak
+        .multipliers
+        .iter()
+        .all(|(days, multiplier)| *days > 0 && *multiplier >= Decimal::one());
+    let valid_price = match &streak.freeze_price {
+        Some(price) => !price.amount.is_zero() && treasury.is_some(),
+        None => true,
+    };
+    if !increasing || !valid_multipliers || !valid_price {
+        return Err(ContractError::InvalidStreakConfig {});
+    }
+    Ok(())
+}
+
+// Freezes can only be bought while the streak is alive, so they never mend a
+// streak that is already broken
+pub fn buy_streak_freeze(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+    let config = CONFIG.load(deps.storage)?;
+    let (price, treasury) = match (config.streak.freeze_price, config.treasury) {
+        (Some(price), Some(treasury)) => (price, treasury),
+        _ => return Err(ContractError::StreakFreezeNotForSale {}),
+    };
+    if info.funds != [price.clone()] {
+        return Err(ContractError::WrongStreakFreezePrice { price });
+    }
+
+    let player = info.sender;
+    let mut streak = PLAYER_STREAKS
+        .may_load(deps.storage, player.clone())?
+        .filter(|streak| streak.is_alive(EmissionSchedule::day(env.block.time)))
+        .ok_or(ContractError::NoActiveStreak {})?;
+    if streak.freezes >= MAX_STREAK_FREEZES {
+        return Err(ContractError::TooManyStreakFreezes {
+            max: MAX_STREAK_FREEZES,
+        });
+    }
+    streak.freezes += 1;
+    PLAYER_STREAKS.save(deps.storage, player.clone(), &streak)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: vec![price],
+        })
+        .add_attribute("action", "buy_streak_freeze")
+        .add_attribute("player", player)
+        .add_attribute("freezes", streak.freezes.to_string()))
+}
+
+// The sender has no referrer yet, so a cycle would only close if the sender
+// were up the referrer's chain
+pub fn register_referrer(
+    deps: DepsMut,
+    info: MessageInfo,
+    referrer: String,
+) -> Result<Response, ContractError> {
+    let player = info.sender;
+    let referrer = deps.api.addr_validate(&referrer)?;
+    if REFERRERS.has(deps.storage, player.clone()) {
+        return Err(ContractError::AlreadyReferred {});
+    }
+    if PLAYER_STATS.has(deps.storage, player.clone()) {
+        return Err(ContractError::AlreadyPlayed {});
+    }
+    let mut upline = Some(referrer.clone());
+    while let Some(address) = upline {
+        if address == player {
+            return Err(ContractError::InvalidReferrer {});
+        }
+        upline = REFERRERS.may_load(deps.storage, address)?;
+    }
+
+    REFERRERS.save(deps.storage, player.clone(), &referrer)?;
+    REFERRALS.save(
+        deps.storage,
+        (referrer.clone(), player.clone()),
+        &Uint128::zero(),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_referrer")
+        .add_attribute("player", player)
+        .add_attribute("referrer", referrer))
+}
+
+pub fn set_referral_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    share: Decimal,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    validate_referral_share(share)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.referral_share = share;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_referral_share")
+        .add_attribute("share", share.to_string()))
+}
+
+pub fn validate_referral_share(share: Decimal) -> Result<(), ContractError> {
+    if share > Decimal::one() {
+        return Err(ContractError::InvalidReferralShare {});
+    }
+    Ok(())
+}
+
+pub fn set_reward_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_token: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let reward_token = deps.api.addr_validate(&reward_token)?;
+
+    // Credited token rewards would otherwise be paid in another token
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        if config.reward_token.is_some() {
+            return Err(ContractError::RewardTokenAlreadySet {});
+        }
+        config.reward_token = Some(reward_token.clone());
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_reward_token")
+        .add_attribute("reward_token", reward_token))
+}
+
+pub fn set_emission(
+    deps: DepsMut,
+    info: MessageInfo,
+    emission: Option<EmissionSchedule>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        if let Some(emission) = &emission {
+            validate_emission(emission, config.reward_token.as_ref())?;
+        }
+        config.emission = emission;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_emission"))
+}
+
+pub fn validate_emission(
+    emission: &EmissionSchedule,
+    reward_token: Option<&Addr>,
+) -> Result<(), ContractError> {
+    if reward_token.is_none() {
+        return Err(ContractError::RewardTokenNotSet {});
+    }
+    if emission.halving_interval == 0 {
+        return Err(ContractError::InvalidEmission {});
+    }
+    Ok(())
+}
+
+pub fn receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+
+    // Only the configured reward token is accepted
+    if CONFIG.load(deps.storage)?.reward_token != Some(info.sender) {
+        return Err(ContractError::UnsupportedToken {});
+    }
+    if wrapper.amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::FundSeason { season_id } => {
+            if SEASONS.load(deps.storage, season_id)?.finalized {
+                return Err(ContractError::SeasonAlreadyFinalized { season_id });
+            }
+            SEASON_TOKEN_POOLS.update(deps.storage, season_id, |pool| -> StdResult<_> {
+                Ok(pool.unwrap_or_default().checked_add(wrapper.amount)?)
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("action", "fund_season")
+                .add_attribute("season_id", season_id.to_string())
+                .add_attribute("sender", wrapper.sender)
+                .add_attribute("amount", wrapper.amount))
+        }
+    }
+}
+
+pub fn set_claim_period(
+    deps: DepsMut,
+    info: MessageInfo,
+    claim_period: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.claim_period = claim_period;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_claim_period"))
+}
+
+pub fn set_payout_table(
+    deps: DepsMut,
+    info: MessageInfo,
+    payout_table: Vec<Decimal>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    validate_payout_table(&payout_table)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.payout_table = payout_table;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_payout_table"))
+}
+
+pub fn validate_payout_table(payout_table: &[Decimal]) -> Result<(), ContractError> {
+    let total = payout_table
+        .iter()
+        .try_fold(Decimal::zero(), |total, share| total.checked_add(*share))
+        .map_err(|_| ContractError::InvalidPayoutTable {})?;
+    if payout_table.len() > MAX_PAYOUT_POSITIONS
+        || payout_table.iter().any(|share| share.is_zero())
+        || total > Decimal::one()
+    {
+        return Err(ContractError::InvalidPayoutTable {});
+    }
+    Ok(())
+}
+
+pub fn transfer_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+    expiry: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Owner)?;
+
+    if expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+        return Err(ContractError::TransferExpired {});
+    }
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+
+    OWNERSHIP.update(deps.storage, |mut ownership| -> Result<_, ContractError> {
+        ownership.pending_owner = Some(new_owner.clone());
+        ownership.pending_expiry = expiry;
+        Ok(ownership)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_ownership")
+        .add_attribute("pending_owner", new_owner))
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let ownership = OWNERSHIP.load(deps.storage)?;
+    match ownership.pending_owner {
+        None => return Err(ContractError::NoPendingTransfer {}),
+        Some(pending_owner) if pending_owner != info.sender => {
+            return Err(ContractError::Unauthorized {})
+        }
+        Some(_) => {}
+    }
+    if ownership
+        .pending_expiry
+        .is_some_and(|expiry| expiry.is_expired(&env.block))
+    {
+        return Err(ContractError::TransferExpired {});
+    }
+
+    OWNERSHIP.save(
+        deps.storage,
+        &Ownership {
+            owner: Some(info.sender.clone()),
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("owner", info.sender))
+}
+
+pub fn renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Owner)?;
+
+    OWNERSHIP.save(
+        deps.storage,
+        &Ownership {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "renounce_ownership"))
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let address = deps.api.addr_validate(&address)?;
+    let members = role_members(role).ok_or(ContractError::OwnerRole {})?;
+    members.save(deps.storage, address.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let address = deps.api.addr_validate(&address)?;
+    let members = role_members(role).ok_or(ContractError::OwnerRole {})?;
+    members.remove(deps.storage, address.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn pause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+    PAUSE.save(
+        deps.storage,
+        &Pause {
+            paused_at: env.block.time,
+            reason: PauseReason::Admin,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("reason", "admin"))
+}
+
+pub fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+    PAUSE.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "unpause"))
+}
+
+pub fn set_submission_ceiling(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_games_per_block: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+    if let Some(max_games_per_block) = max_games_per_block {
+        validate_submission_ceiling(max_games_per_block)?;
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.max_games_per_block = max_games_per_block;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_submission_ceiling"))
+}
+
+pub fn validate_submission_ceiling(max_games_per_block: u64) -> Result<(), ContractError> {
+    if max_games_per_block == 0 {
+        return Err(ContractError::InvalidSubmissionCeiling {});
+    }
+    Ok(())
+}
+
+// Rejects games no human could have played
+fn check_game_rules(rules: &GameRules, score: u64, game_time: u64) -> Result<(), ContractError> {
+    let min = rules.min_game_time.unwrap_or(0);
+    let max = rules.max_game_time.unwrap_or(u64::MAX);
+    if game_time < min || game_time > max {
+        return Err(ContractError::InvalidGameTime { min, max });
+    }
+
+    let by_rate = rules
+        .max_score_per_second
+        .map(|rate| rate.saturating_mul(game_time));
+    let max_score = match (rules.max_score, by_rate) {
+        (Some(cap), Some(by_rate)) => Some(cap.min(by_rate)),
+        (cap, by_rate) => cap.or(by_rate),
+    };
+    if let Some(max) = max_score {
+        if score > max {
+            return Err(ContractError::ScoreTooHigh { max });
+        }
+    }
+
+    Ok(())
+}
+
+// Score the player is ranked by once `score` is recorded
+fn ranked_score(
+    policy: RankPolicy,
+    current: Option<u64>,
+    score: u64,
+) -> Result<u64, ContractError> {
+    let current = match current {
+        Some(current) => current,
+        None => return Ok(score),
+    };
+
+    match policy {
+        RankPolicy::Best => Ok(current.max(score)),
+        RankPolicy::Latest => Ok(score),
+        RankPolicy::Cumulative => current
+            .checked_add(score)
+            .ok_or_else(|| OverflowError::new(OverflowOperation::Add, current, score).into()),
+    }
 }
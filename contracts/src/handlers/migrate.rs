@@ -0,0 +1,174 @@
+use cosmwasm_std::{Decimal, DepsMut, Env, Order, Response, StdResult, Uint128};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION, DEFAULT_CLAIM_PERIOD};
+use crate::error::ContractError;
+use crate::state::model::{
+    Config, Game, GameRules, Ownership, PlayerStats, RankEntry, RankPolicy, SessionLimits,
+    StreakConfig,
+};
+use crate::state::storage::{
+    rank, update_score_counts, ACHIEVEMENT_COUNT, CLUB_COUNT, CONFIG, GAMES, GAME_PLAYERS,
+    LEGACY_GAMES, LEGACY_RANK, OWNERSHIP, PLAYER_STATS, RANKED_PLAYERS, SEASON_COUNT,
+    SESSION_COUNT, TOURNAMENT_COUNT,
+};
+
+type Migration = fn(DepsMut, &Env) -> Result<(), ContractError>;
+
+// State migrations in ascending version order. Each one runs when the stored
+// version is older than its own and it is not newer than the code being deployed.
+const MIGRATIONS: &[(&str, Migration)] = &[("0.2.0", v0_2_0)];
+
+pub fn migrate(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            contract: stored.contract,
+        });
+    }
+
+    let from: Version = stored.version.parse()?;
+    let to: Version = CONTRACT_VERSION.parse()?;
+    if from > to {
+        return Err(ContractError::CannotDowngrade {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+
+    for (version, migration) in MIGRATIONS {
+        let version: Version = version.parse()?;
+        if from < version && version <= to {
+            migration(deps.branch(), &env)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", from.to_string())
+        .add_attribute("to_version", to.to_string()))
+}
+
+// v0.1.0 kept the ranking as a single sorted Vec with one entry per game, only
+// the last game of each player and no config. The contract admin becomes the
+// owner, the legacy ranking is folded into one best-score entry per player and
+// the last games become the start of each player's history.
+fn v0_2_0(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    let info = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address.clone())?;
+    let owner = info.admin.unwrap_or(info.creator);
+    OWNERSHIP.save(
+        deps.storage,
+        &Ownership {
+            owner: Some(deps.api.addr_validate(&owner)?),
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            rank_policy: RankPolicy::Best,
+            server_key: None,
+            // v0.1.0 only knew NewGame, keep its backends recording games
+            session_limits: SessionLimits {
+                sessions_only: false,
+                ..SessionLimits::default()
+            },
+            game_rules: GameRules::default(),
+            payout_table: vec![],
+            claim_period: DEFAULT_CLAIM_PERIOD,
+            reward_token: None,
+            emission: None,
+            house_fee: Decimal::zero(),
+            treasury: None,
+            achievement_nft: None,
+            streak: StreakConfig::default(),
+            referral_share: Decimal::zero(),
+            max_games_per_block: None,
+        },
+    )?;
+
+    let mut ranked_players = 0u64;
+    // Every legacy ranking entry is one game, so they also rebuild the stats.
+    // Game times and play dates were not kept and only the last game time is known.
+    for (score, player) in LEGACY_RANK.may_load(deps.storage)?.unwrap_or_default() {
+        let mut stats = PLAYER_STATS
+            .may_load(deps.storage, player.clone())?
+            .unwrap_or(PlayerStats {
+                games_played: 0,
+                best_score: 0,
+                last_score: 0,
+                total_score: Uint128::zero(),
+                total_game_time: Uint128::zero(),
+                first_played: env.block.time,
+                last_played: env.block.time,
+            });
+        stats.games_played += 1;
+        stats.best_score = stats.best_score.max(score);
+        stats.total_score += Uint128::from(score);
+        PLAYER_STATS.save(deps.storage, player.clone(), &stats)?;
+
+        let current = rank().may_load(deps.storage, player.clone())?;
+        if current.is_none() {
+            ranked_players += 1;
+        }
+        if !matches!(&current, Some(entry) if entry.score >= score) {
+            rank().replace(
+                deps.storage,
+                player,
+                Some(&RankEntry { score }),
+                current.as_ref(),
+            )?;
+            update_score_counts(
+                deps.storage,
+                current.as_ref().map(|entry| entry.score),
+                Some(score),
+            )?;
+        }
+    }
+    LEGACY_RANK.remove(deps.storage);
+    RANKED_PLAYERS.save(deps.storage, &ranked_players)?;
+
+    // Legacy games have no record id nor time. They get ids below the running total,
+    // which counts at least one game per player, and the migration time. The
+    // zero game v0.1.0 wrote for the instantiator is dropped.
+    let legacy = LEGACY_GAMES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut record_id = 0;
+    for (player, game) in legacy {
+        LEGACY_GAMES.remove(deps.storage, player.clone());
+        if game.score == 0 && game.game_time == 0 {
+            continue;
+        }
+        record_id += 1;
+        GAMES.save(
+            deps.storage,
+            (player.clone(), record_id),
+            &Game {
+                score: game.score,
+                game_time: game.game_time,
+                recorded_at: env.block.time,
+            },
+        )?;
+        GAME_PLAYERS.save(deps.storage, record_id, &player)?;
+
+        if let Some(mut stats) = PLAYER_STATS.may_load(deps.storage, player.clone())? {
+            stats.last_score = game.score;
+            stats.total_game_time = game.game_time.into();
+            PLAYER_STATS.save(deps.storage, player, &stats)?;
+        }
+    }
+    SESSION_COUNT.save(deps.storage, &0)?;
+    SEASON_COUNT.save(deps.storage, &0)?;
+    TOURNAMENT_COUNT.save(deps.storage, &0)?;
+    CLUB_COUNT.save(deps.storage, &0)?;
+    ACHIEVEMENT_COUNT.save(deps.storage, &0)?;
+
+    Ok(())
+}
@@ -1,2 +1,4 @@
 pub mod execute;
+pub mod migrate;
 pub mod query;
+pub mod reply;
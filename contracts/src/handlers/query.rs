@@ -1,20 +1,776 @@
-use crate::msg::response::{GetRankResponse, GetScoreByPlayerResponse, GetTotalResponse};
-use crate::state::storage::{GAMES, RANK, TOTAL};
-use cosmwasm_std::{Addr, Deps, StdResult};
+use crate::msg::response::{
+    AchievementInfo, ClubInfo, ClubPayoutInfo, GameInfo, GetClubPayoutsResponse,
+    GetClubRankResponse, GetClubResponse, GetClubTreasuryResponse, GetConfigResponse,
+    GetEmissionStatusResponse, GetGameResponse, GetOwnershipResponse, GetPauseResponse,
+    GetPendingRewardsResponse, GetPlayerAchievementsResponse, GetPlayerClubResponse,
+    GetPlayerHistoryResponse, GetPlayerRankResponse, GetPlayerStatsResponse, GetRankAroundResponse,
+    GetRankResponse, GetRefereesResponse, GetReferralResponse, GetScoreByPlayerResponse,
+    GetSeasonPayoutsResponse, GetSeasonResponse, GetSessionResponse, GetStreakResponse,
+    GetSubmittersResponse, GetTotalResponse, GetTournamentResponse, HasRoleResponse,
+    ListAchievementsResponse, ListGamesResponse, ListRoleMembersResponse, ListSeasonsResponse,
+    ListTournamentsResponse, PayoutInfo, PlayerAchievementInfo, RegisteredGameInfo, SeasonInfo,
+    TournamentInfo,
+};
+use crate::state::model::{DailyEmission, EmissionSchedule, Role, Season, Tournament};
+use crate::state::storage::{
+    active_season, club_members, clubs, game_rank, players_above, rank, registered_game,
+    role_members, season_rank, tournament_rank, ACHIEVEMENTS, CLUB_PAYOUTS, CLUB_TREASURIES,
+    CONFIG, EMISSION_TODAY, GAMES, GAME_LAST_SCORES, GAME_PLAYERS, GAME_SUBMITTERS, GAME_TOTALS,
+    OWNERSHIP, PAUSE, PENDING_REWARDS, PENDING_TOKEN_REWARDS, PLAYER_ACHIEVEMENTS, PLAYER_CLUBS,
+    PLAYER_EMISSION, PLAYER_STATS, PLAYER_STREAKS, RANKED_PLAYERS, REFERRALS, REFERRAL_EARNINGS,
+    REFERRERS, REGISTERED_GAMES, REWARDS_CREDITED_AT, SEASONS, SEASON_CLUB_POOLS,
+    SEASON_CLUB_WINNERS, SEASON_PAYOUTS, SEASON_POOLS, SEASON_TOKEN_POOLS, SEASON_TOTALS, SESSIONS,
+    SUBMITTERS, TOTAL, TOURNAMENTS,
+};
+use cosmwasm_std::{Addr, Coin, Deps, Env, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
 
-pub fn get_rank(deps: Deps) -> StdResult<GetRankResponse> {
-    let rank = RANK.load(deps.storage)?;
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_WINDOW: u32 = 5;
+
+pub fn get_rank(
+    deps: Deps,
+    game_id: Option<String>,
+    season_id: Option<u64>,
+    start_after: Option<(u64, Addr)>,
+    limit: Option<u32>,
+) -> StdResult<GetRankResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // Ranking is read from the highest score down, so the cursor is the upper bound
+    let rank = match (registered_game(game_id), season_id) {
+        (Some(_), Some(_)) => {
+            return Err(StdError::generic_err("Seasons only rank the default game"))
+        }
+        (Some(game_id), None) => {
+            REGISTERED_GAMES.load(deps.storage, &game_id)?;
+            let max = start_after
+                .map(|(score, player)| Bound::exclusive((score, (game_id.clone(), player))));
+            game_rank()
+                .idx
+                .score
+                .sub_prefix(game_id)
+                .range(deps.storage, None, max, Order::Descending)
+                .take(limit)
+                .map(|item| item.map(|((_, player), entry)| (entry.score, player)))
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        (None, Some(season_id)) => {
+            SEASONS.load(deps.storage, season_id)?;
+            let max =
+                start_after.map(|(score, player)| Bound::exclusive((score, (season_id, player))));
+            season_rank()
+                .idx
+                .score
+                .sub_prefix(season_id)
+                .range(deps.storage, None, max, Order::Descending)
+                .take(limit)
+                .map(|item| item.map(|((_, player), entry)| (entry.score, player)))
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        (None, None) => rank()
+            .idx
+            .score
+            .range(
+                deps.storage,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(player, entry)| (entry.score, player)))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
 
     Ok(GetRankResponse { rank })
 }
 
-pub fn get_score_by_player(deps: Deps, player: Addr) -> StdResult<GetScoreByPlayerResponse> {
-    let game = GAMES.load(deps.storage, player)?;
+pub fn get_player_rank(deps: Deps, player: Addr) -> StdResult<GetPlayerRankResponse> {
+    let entry = rank().load(deps.storage, player.clone())?;
+
+    Ok(GetPlayerRankResponse {
+        position: rank_position(deps.storage, &player)?,
+        score: entry.score,
+        total_players: RANKED_PLAYERS.load(deps.storage)?,
+    })
+}
+
+pub fn get_rank_around(
+    deps: Deps,
+    player: Addr,
+    above: Option<u32>,
+    below: Option<u32>,
+) -> StdResult<GetRankAroundResponse> {
+    let above = above.unwrap_or(DEFAULT_WINDOW).min(MAX_LIMIT) as usize;
+    let below = below.unwrap_or(DEFAULT_WINDOW).min(MAX_LIMIT) as usize;
+    let entry = rank().load(deps.storage, player.clone())?;
+    let cursor = (entry.score, player.clone());
+
+    // Only the window is read, walking the score index away from the player
+    let mut window = rank()
+        .idx
+        .score
+        .range(
+            deps.storage,
+            Some(Bound::exclusive(cursor.clone())),
+            None,
+            Order::Ascending,
+        )
+        .take(above)
+        .map(|item| item.map(|(player, entry)| (entry.score, player)))
+        .collect::<StdResult<Vec<_>>>()?;
+    window.reverse();
+    let first_score = window.first().map_or(entry.score, |(score, _)| *score);
+    let start_position = players_above(deps.storage, first_score)? + 1;
+
+    window.push((entry.score, player));
+    for item in rank()
+        .idx
+        .score
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::exclusive(cursor)),
+            Order::Descending,
+        )
+        .take(below)
+    {
+        let (player, entry) = item?;
+        window.push((entry.score, player));
+    }
+
+    Ok(GetRankAroundResponse {
+        start_position,
+        rank: window,
+    })
+}
+
+pub fn get_score_by_player(
+    deps: Deps,
+    player: Addr,
+    game_id: Option<String>,
+) -> StdResult<GetScoreByPlayerResponse> {
+    if let Some(game_id) = registered_game(game_id) {
+        let score = GAME_LAST_SCORES.load(deps.storage, (&game_id, player))?;
+        return Ok(GetScoreByPlayerResponse { score });
+    }
+
+    // Score of the last game the player recorded
+    let (_, game) = GAMES
+        .prefix(player)
+        .range(deps.storage, None, None, Order::Descending)
+        .next()
+        .ok_or_else(|| StdError::not_found("Game"))??;
 
     Ok(GetScoreByPlayerResponse { score: game.score })
 }
 
-pub fn get_total(deps: Deps) -> StdResult<GetTotalResponse> {
-    let total = TOTAL.load(deps.storage)?;
+pub fn get_player_history(
+    deps: Deps,
+    player: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPlayerHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let max = start_after.map(Bound::exclusive);
+
+    let games = GAMES
+        .prefix(player.clone())
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(record_id, game)| GameInfo {
+                record_id,
+                player: player.clone(),
+                score: game.score,
+                game_time: game.game_time,
+                recorded_at: game.recorded_at,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetPlayerHistoryResponse { games })
+}
+
+pub fn get_game(deps: Deps, record_id: u64) -> StdResult<GetGameResponse> {
+    let player = GAME_PLAYERS.load(deps.storage, record_id)?;
+    let game = GAMES.load(deps.storage, (player.clone(), record_id))?;
+
+    Ok(GetGameResponse {
+        game: GameInfo {
+            record_id,
+            player,
+            score: game.score,
+            game_time: game.game_time,
+            recorded_at: game.recorded_at,
+        },
+    })
+}
+
+pub fn get_player_stats(deps: Deps, player: Addr) -> StdResult<GetPlayerStatsResponse> {
+    let stats = PLAYER_STATS.load(deps.storage, player.clone())?;
+    let games_played = Uint128::from(stats.games_played);
+
+    Ok(GetPlayerStatsResponse {
+        games_played: stats.games_played,
+        best_score: stats.best_score,
+        last_score: stats.last_score,
+        average_score: (stats.total_score / games_played).u128() as u64,
+        total_game_time: stats.total_game_time,
+        average_game_time: (stats.total_game_time / games_played).u128() as u64,
+        first_played: stats.first_played,
+        last_played: stats.last_played,
+        rank: rank_position(deps.storage, &player)?,
+    })
+}
+
+// 1-based position of the player, shared by players with the same score
+fn rank_position(storage: &dyn Storage, player: &Addr) -> StdResult<u64> {
+    let entry = rank().load(storage, player.clone())?;
+    Ok(players_above(storage, entry.score)? + 1)
+}
+
+pub fn get_total(deps: Deps, game_id: Option<String>) -> StdResult<GetTotalResponse> {
+    let total = match registered_game(game_id) {
+        Some(game_id) => GAME_TOTALS.load(deps.storage, &game_id)?,
+        None => TOTAL.load(deps.storage)?,
+    };
     Ok(GetTotalResponse { total })
 }
+
+pub fn list_games(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListGamesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+
+    let games = REGISTERED_GAMES
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (game_id, game) = item?;
+            Ok(RegisteredGameInfo {
+                total: GAME_TOTALS.load(deps.storage, &game_id)?,
+                game_id,
+                name: game.name,
+                config: game.config,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ListGamesResponse { games })
+}
+
+pub fn get_config(deps: Deps) -> StdResult<GetConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(GetConfigResponse {
+        rank_policy: config.rank_policy,
+        server_key: config.server_key,
+        session_limits: config.session_limits,
+        game_rules: config.game_rules,
+        payout_table: config.payout_table,
+        claim_period: config.claim_period,
+        reward_token: config.reward_token,
+        emission: config.emission,
+        house_fee: config.house_fee,
+        treasury: config.treasury,
+        achievement_nft: config.achievement_nft,
+        streak: config.streak,
+        referral_share: config.referral_share,
+        max_games_per_block: config.max_games_per_block,
+    })
+}
+
+pub fn get_emission_status(
+    deps: Deps,
+    env: Env,
+    player: Option<Addr>,
+) -> StdResult<GetEmissionStatusResponse> {
+    let emission = CONFIG
+        .load(deps.storage)?
+        .emission
+        .ok_or_else(|| StdError::not_found("Emission schedule"))?;
+    let day = EmissionSchedule::day(env.block.time);
+    let emitted_today = |entry: Option<DailyEmission>| {
+        entry
+            .filter(|entry| entry.day == day)
+            .map(|entry| entry.amount)
+            .unwrap_or_default()
+    };
+
+    let daily_budget = match env.block.time < emission.start {
+        true => Uint128::zero(),
+        false => emission.daily_budget_at(env.block.time),
+    };
+    let emitted = emitted_today(EMISSION_TODAY.may_load(deps.storage)?);
+    let player_remaining_today = player
+        .map(|player| -> StdResult<_> {
+            let earned = emitted_today(PLAYER_EMISSION.may_load(deps.storage, player)?);
+            Ok(emission.player_daily_cap.saturating_sub(earned))
+        })
+        .transpose()?;
+
+    Ok(GetEmissionStatusResponse {
+        day,
+        daily_budget,
+        emitted_today: emitted,
+        remaining_today: daily_budget.saturating_sub(emitted),
+        player_remaining_today,
+    })
+}
+
+pub fn get_pending_rewards(deps: Deps, player: Addr) -> StdResult<GetPendingRewardsResponse> {
+    let claim_period = CONFIG.load(deps.storage)?.claim_period;
+    let rewards = PENDING_REWARDS
+        .may_load(deps.storage, player.clone())?
+        .unwrap_or_default();
+    let tokens = PENDING_TOKEN_REWARDS
+        .may_load(deps.storage, player.clone())?
+        .unwrap_or_default();
+    let claimable_until = REWARDS_CREDITED_AT
+        .may_load(deps.storage, player)?
+        .map(|credited_at| credited_at.plus_seconds(claim_period));
+
+    Ok(GetPendingRewardsResponse {
+        rewards,
+        token_rewards: tokens.transfer.checked_add(tokens.mint)?,
+        claimable_until,
+    })
+}
+
+pub fn get_season(deps: Deps, env: Env, season_id: Option<u64>) -> StdResult<GetSeasonResponse> {
+    let season_id = match season_id {
+        Some(season_id) => season_id,
+        None => active_season(deps.storage, env.block.time)?
+            .ok_or_else(|| StdError::not_found("Active season"))?,
+    };
+    let season = SEASONS.load(deps.storage, season_id)?;
+
+    Ok(GetSeasonResponse {
+        season: season_info(deps.storage, &env, season_id, season)?,
+    })
+}
+
+pub fn list_seasons(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListSeasonsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+
+    let seasons = SEASONS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (season_id, season) = item?;
+            season_info(deps.storage, &env, season_id, season)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ListSeasonsResponse { seasons })
+}
+
+fn season_info(
+    storage: &dyn Storage,
+    env: &Env,
+    season_id: u64,
+    season: Season,
+) -> StdResult<SeasonInfo> {
+    Ok(SeasonInfo {
+        season_id,
+        status: season.status(env.block.time),
+        total_games: SEASON_TOTALS
+            .may_load(storage, season_id)?
+            .unwrap_or_default(),
+        prize_pool: SEASON_POOLS
+            .may_load(storage, season_id)?
+            .unwrap_or_default(),
+        token_pool: SEASON_TOKEN_POOLS
+            .may_load(storage, season_id)?
+            .unwrap_or_default(),
+        club_prize_pool: SEASON_CLUB_POOLS
+            .may_load(storage, season_id)?
+            .unwrap_or_default(),
+        club_winner: SEASON_CLUB_WINNERS.may_load(storage, season_id)?,
+        name: season.name,
+        start: season.start,
+        end: season.end,
+        finalized: season.finalized,
+    })
+}
+
+pub fn get_season_payouts(deps: Deps, season_id: u64) -> StdResult<GetSeasonPayoutsResponse> {
+    // Payouts are bounded by the payout table, no need to paginate
+    let payouts = SEASON_PAYOUTS
+        .prefix(season_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(position, payout)| PayoutInfo {
+                position,
+                player: payout.player,
+                amount: payout.amount,
+                token_amount: payout.token_amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetSeasonPayoutsResponse { payouts })
+}
+
+pub fn get_tournament(deps: Deps, id: u64) -> StdResult<GetTournamentResponse> {
+    let tournament = TOURNAMENTS.load(deps.storage, id)?;
+
+    Ok(GetTournamentResponse {
+        tournament: tournament_info(id, tournament),
+    })
+}
+
+pub fn list_tournaments(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListTournamentsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+
+    let tournaments = TOURNAMENTS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, tournament)| tournament_info(id, tournament)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ListTournamentsResponse { tournaments })
+}
+
+fn tournament_info(tournament_id: u64, tournament: Tournament) -> TournamentInfo {
+    let prize_pool = Coin {
+        amount: tournament.entry_fee.amount * Uint128::from(tournament.players),
+        denom: tournament.entry_fee.denom.clone(),
+    };
+
+    TournamentInfo {
+        tournament_id,
+        entry_fee: tournament.entry_fee,
+        start: tournament.start,
+        end: tournament.end,
+        max_players: tournament.max_players,
+        min_players: tournament.min_players,
+        payout_table: tournament.payout_table,
+        house_fee: tournament.house_fee,
+        players: tournament.players,
+        prize_pool,
+        state: tournament.state,
+    }
+}
+
+pub fn get_tournament_rank(
+    deps: Deps,
+    id: u64,
+    start_after: Option<(u64, Addr)>,
+    limit: Option<u32>,
+) -> StdResult<GetRankResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    TOURNAMENTS.load(deps.storage, id)?;
+    let max = start_after.map(|(score, player)| Bound::exclusive((score, (id, player))));
+
+    let rank = tournament_rank()
+        .idx
+        .score
+        .sub_prefix(id)
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|((_, player), entry)| (entry.score, player)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetRankResponse { rank })
+}
+
+pub fn get_club(
+    deps: Deps,
+    id: u64,
+    start_after: Option<(u64, Addr)>,
+    limit: Option<u32>,
+) -> StdResult<GetClubResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let club = clubs().load(deps.storage, id)?;
+    let max = start_after.map(|(score, player)| Bound::exclusive((score, (id, player))));
+
+    let members = club_members()
+        .idx
+        .score
+        .sub_prefix(id)
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|((_, player), member)| (member.score, player)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetClubResponse {
+        club: ClubInfo {
+            club_id: id,
+            name: club.name,
+            owner: club.owner,
+            open: club.open,
+            max_members: club.max_members,
+            members: club.members,
+            scoring: club.scoring,
+            score: club.score,
+            split: club.split,
+        },
+        members,
+    })
+}
+
+pub fn get_club_rank(
+    deps: Deps,
+    start_after: Option<(u64, u64)>,
+    limit: Option<u32>,
+) -> StdResult<GetClubRankResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let rank = clubs()
+        .idx
+        .score
+        .range(
+            deps.storage,
+            None,
+            start_after.map(Bound::exclusive),
+            Order::Descending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(club_id, club)| (club.score, club_id)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetClubRankResponse { rank })
+}
+
+pub fn list_achievements(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAchievementsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+
+    let achievements = ACHIEVEMENTS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(achievement_id, achievement)| AchievementInfo {
+                achievement_id,
+                name: achievement.name,
+                description: achievement.description,
+                rule: achievement.rule,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ListAchievementsResponse { achievements })
+}
+
+pub fn get_player_achievements(
+    deps: Deps,
+    player: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetPlayerAchievementsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+
+    let achievements = PLAYER_ACHIEVEMENTS
+        .prefix(player)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(achievement_id, earned)| PlayerAchievementInfo {
+                achievement_id,
+                token_id: earned.token_id,
+                earned_at: earned.earned_at,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetPlayerAchievementsResponse { achievements })
+}
+
+pub fn get_club_treasury(deps: Deps, id: u64) -> StdResult<GetClubTreasuryResponse> {
+    clubs().load(deps.storage, id)?;
+
+    Ok(GetClubTreasuryResponse {
+        balance: CLUB_TREASURIES
+            .may_load(deps.storage, id)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn get_club_payouts(
+    deps: Deps,
+    id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GetClubPayoutsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let max = start_after.map(Bound::exclusive);
+
+    let payouts = CLUB_PAYOUTS
+        .prefix(id)
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(payout_id, payout)| ClubPayoutInfo {
+                payout_id,
+                distributed_at: payout.distributed_at,
+                shares: payout.shares,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetClubPayoutsResponse { payouts })
+}
+
+pub fn get_player_club(deps: Deps, player: Addr) -> StdResult<GetPlayerClubResponse> {
+    Ok(GetPlayerClubResponse {
+        club_id: PLAYER_CLUBS.may_load(deps.storage, player)?,
+    })
+}
+
+pub fn get_referral(deps: Deps, player: Addr) -> StdResult<GetReferralResponse> {
+    Ok(GetReferralResponse {
+        referrer: REFERRERS.may_load(deps.storage, player.clone())?,
+        earnings: REFERRAL_EARNINGS
+            .may_load(deps.storage, player)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn get_referees(
+    deps: Deps,
+    referrer: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GetRefereesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|address| Bound::exclusive(Addr::unchecked(address)));
+
+    let referees = REFERRALS
+        .prefix(referrer)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GetRefereesResponse { referees })
+}
+
+pub fn get_streak(deps: Deps, env: Env, player: Addr) -> StdResult<GetStreakResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let streak = PLAYER_STREAKS.may_load(deps.storage, player)?;
+    let today = EmissionSchedule::day(env.block.time);
+    let current = streak
+        .as_ref()
+        .filter(|streak| streak.is_alive(today))
+        .map_or(0, |streak| streak.current);
+
+    Ok(GetStreakResponse {
+        current,
+        longest: streak.as_ref().map_or(0, |streak| streak.longest),
+        last_day: streak.as_ref().map(|streak| streak.last_day),
+        freezes: streak.as_ref().map_or(0, |streak| streak.freezes),
+        multiplier: config.streak.multiplier(current),
+    })
+}
+
+pub fn get_session(deps: Deps, session_id: u64) -> StdResult<GetSessionResponse> {
+    let session = SESSIONS.load(deps.storage, session_id)?;
+
+    Ok(GetSessionResponse {
+        player: session.player,
+        started_at: session.started_at,
+        ended_at: session.ended_at,
+    })
+}
+
+pub fn get_ownership(deps: Deps) -> StdResult<GetOwnershipResponse> {
+    let ownership = OWNERSHIP.load(deps.storage)?;
+
+    Ok(GetOwnershipResponse {
+        owner: ownership.owner,
+        pending_owner: ownership.pending_owner,
+        pending_expiry: ownership.pending_expiry,
+    })
+}
+
+pub fn has_role(deps: Deps, role: Role, address: Addr) -> StdResult<HasRoleResponse> {
+    let owner = OWNERSHIP.load(deps.storage)?.owner;
+    let has_role = match role_members(role) {
+        Some(members) if members.has(deps.storage, address.clone()) => true,
+        _ => role != Role::ScoreSubmitter && owner == Some(address),
+    };
+
+    Ok(HasRoleResponse { role, has_role })
+}
+
+pub fn list_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListRoleMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after
+        .as_ref()
+        .map(|address| Bound::exclusive(Addr::unchecked(address)));
+
+    let members = match role_members(role) {
+        Some(members) => members
+            .keys(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?,
+        None => OWNERSHIP
+            .load(deps.storage)?
+            .owner
+            .into_iter()
+            .filter(|owner| start_after.as_deref() < Some(owner.as_str()))
+            .collect(),
+    };
+
+    Ok(ListRoleMembersResponse { members })
+}
+
+pub fn get_pause(deps: Deps) -> StdResult<GetPauseResponse> {
+    let pause = PAUSE.may_load(deps.storage)?;
+
+    Ok(GetPauseResponse {
+        paused: pause.is_some(),
+        paused_at: pause.as_ref().map(|pause| pause.paused_at),
+        reason: pause.map(|pause| pause.reason),
+    })
+}
+
+pub fn get_submitters(
+    deps: Deps,
+    game_id: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GetSubmittersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|address| Bound::exclusive(Addr::unchecked(address)));
+
+    let submitters = match registered_game(game_id) {
+        Some(game_id) => GAME_SUBMITTERS
+            .prefix(&game_id)
+            .keys(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?,
+        None => SUBMITTERS
+            .keys(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    Ok(GetSubmittersResponse { submitters })
+}
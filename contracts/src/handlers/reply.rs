@@ -0,0 +1,18 @@
+use cosmwasm_std::{DepsMut, Env, Reply, Response};
+
+use crate::error::ContractError;
+
+// Achievement mints only reply when they fail
+pub const MINT_ACHIEVEMENT_REPLY_ID: u64 = 1;
+
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // A failing NFT contract must not cost the player the game that
+        // earned the achievement. The unlock stays recorded and the error is
+        // only reported
+        MINT_ACHIEVEMENT_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "achievement_mint_failed")
+            .add_attribute("error", msg.result.into_result().err().unwrap_or_default())),
+        id => Err(ContractError::UnknownReply { id }),
+    }
+}
@@ -1,11 +1,259 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_json_vec, Addr, Binary, Coin, Decimal, StdResult, Timestamp};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::Expiration;
+use sha2::{Digest, Sha256};
+
+use crate::state::model::{
+    AchievementRule, ClubScoring, ClubSplit, EmissionSchedule, GameConfig, GameRules, Role,
+    ServerKey, SessionLimits, StreakConfig,
+};
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Records a game sent by a submitter, who supplies the `game_time`.
+    /// Refused for the default game while the session limits require
+    /// sessions, which they do by default
     NewGame {
         player: Addr,
         score: u64,
         game_time: u64,
+        /// Registered game the score is for, the default game when not set
+        game_id: Option<String>,
+    },
+    /// Records a game attested by the game server, sent by the player. Like
+    /// `NewGame`, its `game_time` is trusted and it is refused while the
+    /// session limits require sessions
+    SubmitSignedGame {
+        score: u64,
+        game_time: u64,
+        nonce: u64,
+        signature: Binary,
+    },
+    /// Opens a session for the sender, timed with the block time
+    StartGame {},
+    /// Closes a session with its score, sent by a submitter
+    EndGame {
+        session_id: u64,
+        score: u64,
+    },
+    AddSubmitter {
+        address: String,
+        game_id: Option<String>,
+    },
+    RemoveSubmitter {
+        address: String,
+        game_id: Option<String>,
+    },
+    /// Registers a game with its own leaderboard, or updates its name and config
+    RegisterGame {
+        game_id: String,
+        name: String,
+        config: GameConfig,
+    },
+    SetServerKey {
+        server_key: Option<ServerKey>,
+    },
+    SetSessionLimits {
+        session_limits: SessionLimits,
+    },
+    SetGameRules {
+        game_rules: GameRules,
+    },
+    /// Schedules a season, which cannot overlap the previous one
+    CreateSeason {
+        name: String,
+        start: Timestamp,
+        end: Timestamp,
+    },
+    /// Adds the sent funds to the prize pool of a season
+    FundSeason {
+        season_id: u64,
+    },
+    /// Adds the sent funds to the club prize of a season, which goes to the
+    /// treasury of the club whose members scored the most in the season
+    FundSeasonClubPrize {
+        season_id: u64,
+    },
+    /// Pays the prize pool of a finished season out to its leaderboard
+    FinalizeSeason {
+        season_id: u64,
+    },
+    SetPayoutTable {
+        payout_table: Vec<Decimal>,
+    },
+    /// Sends the sender every reward credited to them
+    ClaimRewards {},
+    /// Moves rewards unclaimed past the claim period to the treasury, or to
+    /// the owner when no treasury is set
+    SweepRewards {
+        players: Vec<String>,
+    },
+    SetClaimPeriod {
+        claim_period: u64,
+    },
+    /// Sets the CW20 reward token, which cannot be changed afterwards
+    SetRewardToken {
+        reward_token: String,
+    },
+    /// Sets the CW721 contract achievements are minted in, `None` disables them
+    SetAchievementNft {
+        nft: Option<String>,
+    },
+    AddAchievement {
+        name: String,
+        description: String,
+        rule: AchievementRule,
+    },
+    /// Stops awarding an achievement, NFTs already minted are kept
+    RemoveAchievement {
+        id: u64,
+    },
+    /// Sets the streak multipliers and the price of a streak freeze, a price
+    /// requires the treasury
+    SetStreakConfig {
+        streak: StreakConfig,
+    },
+    /// Buys a freeze that keeps the sender's streak going over a day without
+    /// games, paid to the treasury
+    BuyStreakFreeze {},
+    /// Sets the sender's referrer, once and before their first game
+    RegisterReferrer {
+        referrer: String,
+    },
+    /// Sets the share of referee earnings credited to referrers
+    SetReferralShare {
+        share: Decimal,
+    },
+    /// Opens a tournament players join by paying `entry_fee`
+    CreateTournament {
+        entry_fee: Coin,
+        start: Timestamp,
+        end: Timestamp,
+        max_players: u32,
+        /// Defaults to the number of paid positions
+        min_players: Option<u32>,
+        payout_table: Vec<Decimal>,
+    },
+    /// Sent with exactly the entry fee, before the tournament starts
+    JoinTournament {
+        id: u64,
+    },
+    /// Pays the prize pool of a finished tournament out to its leaderboard
+    SettleTournament {
+        id: u64,
     },
+    CancelTournament {
+        id: u64,
+    },
+    /// Returns the entry fee of a cancelled or under-subscribed tournament
+    RefundTournament {
+        id: u64,
+    },
+    /// Creates a club owned by the sender, who becomes its first member
+    CreateClub {
+        name: String,
+        open: bool,
+        max_members: u32,
+        scoring: ClubScoring,
+    },
+    /// Lets a player join an invite only club, sent by the club owner
+    InviteToClub {
+        id: u64,
+        player: String,
+    },
+    JoinClub {
+        id: u64,
+    },
+    /// Leaves the sender's club, the club is removed with its last member
+    LeaveClub {},
+    /// Adds the sent funds to a club treasury
+    DepositToClub {
+        id: u64,
+    },
+    /// Sets how the club treasury is shared, sent by the club owner
+    SetClubSplit {
+        id: u64,
+        split: ClubSplit,
+    },
+    /// Credits the club treasury to the members, sent by the club owner
+    DistributeClubTreasury {
+        id: u64,
+    },
+    SetHouseFee {
+        house_fee: Decimal,
+        treasury: Option<String>,
+    },
+    /// Sets how many reward tokens games earn, requires the reward token
+    SetEmission {
+        emission: Option<EmissionSchedule>,
+    },
+    /// Reward tokens sent with a `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+    /// Proposes a new owner, who has to accept before `expiry`
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    /// Leaves the contract without owner, disabling every admin message
+    RenounceOwnership {},
+    /// Owner gives an account a role, except the owner role
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Stops game submissions and every message that pays out rewards
+    Pause {},
+    Unpause {},
+    /// Sets how many games a block may record before the contract pauses
+    /// itself, `None` disables the circuit breaker
+    SetSubmissionCeiling {
+        max_games_per_block: Option<u64>,
+    },
+}
+
+/// Messages sent along with reward tokens through `Cw20ExecuteMsg::Send`
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Adds the tokens to the prize pool of a season
+    FundSeason { season_id: u64 },
+}
+
+/// Extension of the minted achievement NFTs, following the on-chain metadata
+/// layout of `cw721-metadata-onchain`
+#[cw_serde]
+pub struct AchievementMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+}
+
+#[cw_serde]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Payload the game server signs for `SubmitSignedGame`
+#[cw_serde]
+pub struct ScoreAttestation {
+    pub player: Addr,
+    pub score: u64,
+    pub game_time: u64,
+    pub nonce: u64,
+    pub chain_id: String,
+    pub contract_addr: Addr,
+}
+
+impl ScoreAttestation {
+    /// SHA-256 of the JSON encoded attestation, which is what gets signed
+    pub fn hash(&self) -> StdResult<Vec<u8>> {
+        Ok(Sha256::digest(to_json_vec(self)?).to_vec())
+    }
 }
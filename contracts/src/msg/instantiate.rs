@@ -1,4 +1,44 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+
+use crate::state::model::{
+    EmissionSchedule, GameRules, RankPolicy, ServerKey, SessionLimits, StreakConfig,
+};
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// Defaults to the instantiator
+    pub owner: Option<String>,
+    /// Accounts allowed to record games, e.g. the game backend
+    pub submitters: Option<Vec<String>>,
+    /// Defaults to `RankPolicy::Best`
+    pub rank_policy: Option<RankPolicy>,
+    /// Key used to verify `SubmitSignedGame` attestations
+    pub server_key: Option<ServerKey>,
+    /// Defaults to `SessionLimits::default()`, which only takes default game
+    /// plays through sessions
+    pub session_limits: Option<SessionLimits>,
+    /// Defaults to no limits
+    pub game_rules: Option<GameRules>,
+    /// Defaults to no payouts
+    pub payout_table: Option<Vec<Decimal>>,
+    /// Defaults to `DEFAULT_CLAIM_PERIOD`
+    pub claim_period: Option<u64>,
+    /// CW20 contract token rewards are paid in
+    pub reward_token: Option<String>,
+    /// Requires `reward_token`, defaults to no earnings
+    pub emission: Option<EmissionSchedule>,
+    /// Defaults to no fee
+    pub house_fee: Option<Decimal>,
+    /// Account receiving the house fee
+    pub treasury: Option<String>,
+    /// CW721 contract achievements are minted in, the contract must be its minter
+    pub achievement_nft: Option<String>,
+    /// Defaults to no multipliers and no freezes for sale
+    pub streak: Option<StreakConfig>,
+    /// Defaults to no referral earnings
+    pub referral_share: Option<Decimal>,
+    /// Defaults to no circuit breaker
+    pub max_games_per_block: Option<u64>,
+}
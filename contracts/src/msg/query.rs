@@ -1,16 +1,207 @@
-use crate::msg::response::{GetRankResponse, GetScoreByPlayerResponse, GetTotalResponse};
+use crate::msg::response::{
+    GetClubPayoutsResponse, GetClubRankResponse, GetClubResponse, GetClubTreasuryResponse,
+    GetConfigResponse, GetEmissionStatusResponse, GetGameResponse, GetOwnershipResponse,
+    GetPauseResponse, GetPendingRewardsResponse, GetPlayerAchievementsResponse,
+    GetPlayerClubResponse, GetPlayerHistoryResponse, GetPlayerRankResponse, GetPlayerStatsResponse,
+    GetRankAroundResponse, GetRankResponse, GetRefereesResponse, GetReferralResponse,
+    GetScoreByPlayerResponse, GetSeasonPayoutsResponse, GetSeasonResponse, GetSessionResponse,
+    GetStreakResponse, GetSubmittersResponse, GetTotalResponse, GetTournamentResponse,
+    HasRoleResponse, ListAchievementsResponse, ListGamesResponse, ListRoleMembersResponse,
+    ListSeasonsResponse, ListTournamentsResponse,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 
+use crate::state::model::Role;
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    /// Global leaderboard, or the one of a season when `season_id` is set.
+    /// `game_id` picks a registered game, seasons only rank the default game
     #[returns(GetRankResponse)]
-    GetRank {},
+    GetRank {
+        game_id: Option<String>,
+        season_id: Option<u64>,
+        start_after: Option<(u64, Addr)>,
+        limit: Option<u32>,
+    },
+
+    /// Leaderboard position of a player, starting at 1 and shared by players
+    /// with the same score
+    #[returns(GetPlayerRankResponse)]
+    GetPlayerRank { player: Addr },
+
+    /// Leaderboard entries right above and below a player, the player included
+    #[returns(GetRankAroundResponse)]
+    GetRankAround {
+        player: Addr,
+        above: Option<u32>,
+        below: Option<u32>,
+    },
 
     #[returns(GetScoreByPlayerResponse)]
-    GetScoreByPlayer { player: Addr },
+    GetScoreByPlayer {
+        player: Addr,
+        game_id: Option<String>,
+    },
 
     #[returns(GetTotalResponse)]
-    GetTotal {},
+    GetTotal { game_id: Option<String> },
+
+    /// Registered games, without the default one
+    #[returns(ListGamesResponse)]
+    ListGames {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Default game plays of the player, newest first. Plays on registered
+    /// games keep no history
+    #[returns(GetPlayerHistoryResponse)]
+    GetPlayerHistory {
+        player: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// A default game play by its record id, the running total when it was
+    /// recorded. Not to be confused with the `game_id` of registered games
+    #[returns(GetGameResponse)]
+    GetGame { record_id: u64 },
+
+    /// Stats of the player on the default game, registered games keep none
+    #[returns(GetPlayerStatsResponse)]
+    GetPlayerStats { player: Addr },
+
+    /// A season by id, or the active one when `season_id` is not set
+    #[returns(GetSeasonResponse)]
+    GetSeason { season_id: Option<u64> },
+
+    /// Prizes paid when the season was finalized, by leaderboard position
+    #[returns(GetSeasonPayoutsResponse)]
+    GetSeasonPayouts { season_id: u64 },
+
+    #[returns(ListSeasonsResponse)]
+    ListSeasons {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(ListAchievementsResponse)]
+    ListAchievements {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Achievements the player earned, with the id of each minted NFT
+    #[returns(GetPlayerAchievementsResponse)]
+    GetPlayerAchievements {
+        player: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Rewards the player can claim and until when
+    #[returns(GetPendingRewardsResponse)]
+    GetPendingRewards { player: Addr },
+
+    /// Tokens games can still earn today, overall and for `player`
+    #[returns(GetEmissionStatusResponse)]
+    GetEmissionStatus { player: Option<Addr> },
+
+    #[returns(GetTournamentResponse)]
+    GetTournament { id: u64 },
+
+    #[returns(ListTournamentsResponse)]
+    ListTournaments {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Leaderboard of a tournament, paginated like `GetRank`
+    #[returns(GetRankResponse)]
+    GetTournamentRank {
+        id: u64,
+        start_after: Option<(u64, Addr)>,
+        limit: Option<u32>,
+    },
+
+    /// A club with a page of its members, paginated like `GetRank`
+    #[returns(GetClubResponse)]
+    GetClub {
+        id: u64,
+        start_after: Option<(u64, Addr)>,
+        limit: Option<u32>,
+    },
+
+    /// Club leaderboard, `start_after` is the last (score, club_id) returned
+    #[returns(GetClubRankResponse)]
+    GetClubRank {
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetClubTreasuryResponse)]
+    GetClubTreasury { id: u64 },
+
+    /// Distributions of a club treasury, newest first
+    #[returns(GetClubPayoutsResponse)]
+    GetClubPayouts {
+        id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetPlayerClubResponse)]
+    GetPlayerClub { player: Addr },
+
+    /// Referrer of the player and tokens their referees earned them
+    #[returns(GetReferralResponse)]
+    GetReferral { player: Addr },
+
+    /// Players the referrer referred, with the tokens each one earned them
+    #[returns(GetRefereesResponse)]
+    GetReferees {
+        referrer: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Daily play streak of the player, broken streaks read as 0
+    #[returns(GetStreakResponse)]
+    GetStreak { player: Addr },
+
+    #[returns(GetConfigResponse)]
+    GetConfig {},
+
+    #[returns(GetOwnershipResponse)]
+    GetOwnership {},
+
+    /// Whether the account holds the role, the owner holds every role but
+    /// the score submitter
+    #[returns(HasRoleResponse)]
+    HasRole { role: Role, address: Addr },
+
+    /// Accounts granted the role, without the owner unless the role is owner
+    #[returns(ListRoleMembersResponse)]
+    ListRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Whether the contract is paused, since when and why
+    #[returns(GetPauseResponse)]
+    GetPause {},
+
+    #[returns(GetSessionResponse)]
+    GetSession { session_id: u64 },
+
+    #[returns(GetSubmittersResponse)]
+    GetSubmitters {
+        game_id: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
@@ -1,5 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
+use cw_utils::Expiration;
+
+use crate::state::model::{
+    AchievementRule, ClubScoring, ClubSplit, EmissionSchedule, GameConfig, GameRules, PauseReason,
+    RankPolicy, Role, SeasonStatus, ServerKey, SessionLimits, StreakConfig, TournamentState,
+};
 
 // We define a custom struct for each query response
 #[cw_serde]
@@ -7,6 +13,20 @@ pub struct GetRankResponse {
     pub rank: Vec<(u64, Addr)>,
 }
 
+#[cw_serde]
+pub struct GetPlayerRankResponse {
+    pub position: u64,
+    pub score: u64,
+    pub total_players: u64,
+}
+
+#[cw_serde]
+pub struct GetRankAroundResponse {
+    /// Position of the first entry of `rank`
+    pub start_position: u64,
+    pub rank: Vec<(u64, Addr)>,
+}
+
 #[cw_serde]
 pub struct GetScoreByPlayerResponse {
     pub score: u64,
@@ -16,3 +36,293 @@ pub struct GetScoreByPlayerResponse {
 pub struct GetTotalResponse {
     pub total: u64,
 }
+
+#[cw_serde]
+pub struct GameInfo {
+    /// Id of the play, not the `game_id` of a registered game
+    pub record_id: u64,
+    pub player: Addr,
+    pub score: u64,
+    pub game_time: u64,
+    pub recorded_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct GetPlayerHistoryResponse {
+    pub games: Vec<GameInfo>,
+}
+
+#[cw_serde]
+pub struct GetGameResponse {
+    pub game: GameInfo,
+}
+
+#[cw_serde]
+pub struct GetPlayerStatsResponse {
+    pub games_played: u64,
+    pub best_score: u64,
+    pub last_score: u64,
+    pub average_score: u64,
+    pub total_game_time: Uint128,
+    pub average_game_time: u64,
+    pub first_played: Timestamp,
+    pub last_played: Timestamp,
+    /// Position on the leaderboard, starting at 1 and shared by players with
+    /// the same score. Read from the score prefix counts, without walking
+    /// the leaderboard
+    pub rank: u64,
+}
+
+#[cw_serde]
+pub struct SeasonInfo {
+    pub season_id: u64,
+    pub name: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub status: SeasonStatus,
+    pub total_games: u64,
+    /// Funds still to be paid out, or left over once finalized
+    pub prize_pool: Vec<Coin>,
+    /// Same as `prize_pool` for the CW20 reward token
+    pub token_pool: Uint128,
+    /// Funds for the top club of the season, still to be paid out or left
+    /// over when no club member played
+    pub club_prize_pool: Vec<Coin>,
+    /// Club that got the club prize once finalized
+    pub club_winner: Option<u64>,
+    pub finalized: bool,
+}
+
+#[cw_serde]
+pub struct GetSeasonResponse {
+    pub season: SeasonInfo,
+}
+
+#[cw_serde]
+pub struct PayoutInfo {
+    pub position: u64,
+    pub player: Addr,
+    pub amount: Vec<Coin>,
+    pub token_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct GetSeasonPayoutsResponse {
+    pub payouts: Vec<PayoutInfo>,
+}
+
+#[cw_serde]
+pub struct ListSeasonsResponse {
+    pub seasons: Vec<SeasonInfo>,
+}
+
+#[cw_serde]
+pub struct GetPendingRewardsResponse {
+    pub rewards: Vec<Coin>,
+    /// CW20 reward tokens, whether transferred or minted on claim
+    pub token_rewards: Uint128,
+    /// After this time the owner may sweep the rewards, `None` when nothing is pending
+    pub claimable_until: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct GetEmissionStatusResponse {
+    pub day: u64,
+    /// Today's budget, after halvings
+    pub daily_budget: Uint128,
+    pub emitted_today: Uint128,
+    pub remaining_today: Uint128,
+    /// What is left of the player's daily cap, when a player was given
+    pub player_remaining_today: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct TournamentInfo {
+    pub tournament_id: u64,
+    pub entry_fee: Coin,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub max_players: u32,
+    pub min_players: u32,
+    pub payout_table: Vec<Decimal>,
+    pub house_fee: Decimal,
+    pub players: u32,
+    /// Entry fees paid by the players
+    pub prize_pool: Coin,
+    pub state: TournamentState,
+}
+
+#[cw_serde]
+pub struct GetTournamentResponse {
+    pub tournament: TournamentInfo,
+}
+
+#[cw_serde]
+pub struct RegisteredGameInfo {
+    pub game_id: String,
+    pub name: String,
+    pub config: GameConfig,
+    pub total: u64,
+}
+
+#[cw_serde]
+pub struct ListGamesResponse {
+    pub games: Vec<RegisteredGameInfo>,
+}
+
+#[cw_serde]
+pub struct ClubInfo {
+    pub club_id: u64,
+    pub name: String,
+    pub owner: Addr,
+    pub open: bool,
+    pub max_members: u32,
+    pub members: u32,
+    pub scoring: ClubScoring,
+    pub score: u64,
+    pub split: ClubSplit,
+}
+
+#[cw_serde]
+pub struct GetClubTreasuryResponse {
+    pub balance: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct ClubPayoutInfo {
+    pub payout_id: u64,
+    pub distributed_at: Timestamp,
+    pub shares: Vec<(Addr, Vec<Coin>)>,
+}
+
+#[cw_serde]
+pub struct GetClubPayoutsResponse {
+    pub payouts: Vec<ClubPayoutInfo>,
+}
+
+#[cw_serde]
+pub struct GetClubResponse {
+    pub club: ClubInfo,
+    /// Page of (best score, player), highest score first
+    pub members: Vec<(u64, Addr)>,
+}
+
+#[cw_serde]
+pub struct GetClubRankResponse {
+    /// (score, club_id), highest score first
+    pub rank: Vec<(u64, u64)>,
+}
+
+#[cw_serde]
+pub struct GetPlayerClubResponse {
+    pub club_id: Option<u64>,
+}
+
+#[cw_serde]
+pub struct GetReferralResponse {
+    pub referrer: Option<Addr>,
+    /// Tokens credited to the player for their referees
+    pub earnings: Uint128,
+}
+
+#[cw_serde]
+pub struct GetRefereesResponse {
+    pub referees: Vec<(Addr, Uint128)>,
+}
+
+#[cw_serde]
+pub struct GetStreakResponse {
+    pub current: u32,
+    pub longest: u32,
+    /// Day of the last game, in days since the Unix epoch
+    pub last_day: Option<u64>,
+    pub freezes: u32,
+    /// Multiplier on the tokens earned at the current streak
+    pub multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct AchievementInfo {
+    pub achievement_id: u64,
+    pub name: String,
+    pub description: String,
+    pub rule: AchievementRule,
+}
+
+#[cw_serde]
+pub struct ListAchievementsResponse {
+    pub achievements: Vec<AchievementInfo>,
+}
+
+#[cw_serde]
+pub struct PlayerAchievementInfo {
+    pub achievement_id: u64,
+    /// Id of the NFT minted for the achievement
+    pub token_id: String,
+    pub earned_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct GetPlayerAchievementsResponse {
+    pub achievements: Vec<PlayerAchievementInfo>,
+}
+
+#[cw_serde]
+pub struct ListTournamentsResponse {
+    pub tournaments: Vec<TournamentInfo>,
+}
+
+#[cw_serde]
+pub struct GetConfigResponse {
+    pub rank_policy: RankPolicy,
+    pub server_key: Option<ServerKey>,
+    pub session_limits: SessionLimits,
+    pub game_rules: GameRules,
+    pub payout_table: Vec<Decimal>,
+    pub claim_period: u64,
+    pub reward_token: Option<Addr>,
+    pub emission: Option<EmissionSchedule>,
+    pub house_fee: Decimal,
+    pub treasury: Option<Addr>,
+    pub achievement_nft: Option<Addr>,
+    pub streak: StreakConfig,
+    pub referral_share: Decimal,
+    pub max_games_per_block: Option<u64>,
+}
+
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub role: Role,
+    pub has_role: bool,
+}
+
+#[cw_serde]
+pub struct ListRoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct GetPauseResponse {
+    pub paused: bool,
+    pub paused_at: Option<Timestamp>,
+    pub reason: Option<PauseReason>,
+}
+
+#[cw_serde]
+pub struct GetSessionResponse {
+    pub player: Addr,
+    pub started_at: Timestamp,
+    pub ended_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct GetSubmittersResponse {
+    pub submitters: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct GetOwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
@@ -1,3 +1,5 @@
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -5,4 +7,491 @@ use serde::{Deserialize, Serialize};
 pub struct Game {
     pub score: u64,
     pub game_time: u64,
+    pub recorded_at: Timestamp,
+}
+
+/// Game layout up to v0.1.0, only read when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LegacyGame {
+    pub score: u64,
+    pub game_time: u64,
+}
+
+/// Aggregates kept up to date on every game of a player
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PlayerStats {
+    pub games_played: u64,
+    pub best_score: u64,
+    pub last_score: u64,
+    pub total_score: Uint128,
+    pub total_game_time: Uint128,
+    pub first_played: Timestamp,
+    pub last_played: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RankEntry {
+    pub score: u64,
+}
+
+/// Entry of a season leaderboard, which carries its season for the score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SeasonRankEntry {
+    pub season_id: u64,
+    pub score: u64,
+}
+
+/// How a new game changes the player's position on the leaderboard
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RankPolicy {
+    /// Keep the highest score the player ever made
+    #[default]
+    Best,
+    /// Keep the score of the last game
+    Latest,
+    /// Add up the scores of every game
+    Cumulative,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    Secp256k1,
+    Ed25519,
+}
+
+/// Public key of the game server that signs score attestations
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ServerKey {
+    pub scheme: SignatureScheme,
+    pub pubkey: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub rank_policy: RankPolicy,
+    pub server_key: Option<ServerKey>,
+    pub session_limits: SessionLimits,
+    pub game_rules: GameRules,
+    /// Share of the season prize pool paid to each leaderboard position,
+    /// starting at the first one
+    pub payout_table: Vec<Decimal>,
+    /// Seconds a player has to claim rewards after the last credit, before
+    /// the owner can sweep them
+    pub claim_period: u64,
+    /// CW20 contract token rewards are paid in
+    pub reward_token: Option<Addr>,
+    /// Reward tokens earned by every game, `None` disables earnings
+    pub emission: Option<EmissionSchedule>,
+    /// Share of tournament prize pools sent to the treasury
+    pub house_fee: Decimal,
+    pub treasury: Option<Addr>,
+    /// CW721 contract achievements are minted in, `None` disables achievements
+    pub achievement_nft: Option<Addr>,
+    pub streak: StreakConfig,
+    /// Share of the tokens a referee earns credited on top to the referrer
+    pub referral_share: Decimal,
+    /// Games a block may record before the contract pauses itself, `None`
+    /// disables the circuit breaker
+    pub max_games_per_block: Option<u64>,
+}
+
+/// How many reward tokens games earn, minted when the player claims them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct EmissionSchedule {
+    /// Tokens earned per point of score
+    pub tokens_per_point: Decimal,
+    /// Most tokens a player can earn in a day
+    pub player_daily_cap: Uint128,
+    /// Tokens all players together can earn in a day, before any halving
+    pub daily_budget: Uint128,
+    /// Seconds after which the daily budget is halved, counted from `start`
+    pub halving_interval: u64,
+    /// Games recorded before this time earn nothing
+    pub start: Timestamp,
+}
+
+impl EmissionSchedule {
+    /// Days are counted in UTC from the Unix epoch
+    pub fn day(now: Timestamp) -> u64 {
+        now.seconds() / 86_400
+    }
+
+    pub fn daily_budget_at(&self, now: Timestamp) -> Uint128 {
+        let halvings = now.seconds().saturating_sub(self.start.seconds()) / self.halving_interval;
+        u32::try_from(halvings)
+            .ok()
+            .and_then(|halvings| self.daily_budget.checked_shr(halvings).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// How daily play streaks pay off
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct StreakConfig {
+    /// Multiplier on the tokens a game earns, by the streak length in days it
+    /// starts at, in increasing order of days
+    pub multipliers: Vec<(u32, Decimal)>,
+    /// Price of a streak freeze, `None` stops selling them
+    pub freeze_price: Option<Coin>,
+}
+
+impl StreakConfig {
+    pub fn multiplier(&self, days: u32) -> Decimal {
+        self.multipliers
+            .iter()
+            .rev()
+            .find(|(min_days, _)| days >= *min_days)
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(Decimal::one())
+    }
+}
+
+/// Consecutive days, in UTC, a player played on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Streak {
+    pub current: u32,
+    pub longest: u32,
+    /// Day of the last game, counted as in `EmissionSchedule::day`
+    pub last_day: u64,
+    /// Freezes held, each one covers a day without games
+    pub freezes: u32,
+}
+
+impl Streak {
+    /// Whether the streak can still go on with a game on `day`
+    pub fn is_alive(&self, day: u64) -> bool {
+        day <= self.last_day + 1 + u64::from(self.freezes)
+    }
+}
+
+/// Tokens emitted during a day
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DailyEmission {
+    pub day: u64,
+    pub amount: Uint128,
+}
+
+/// Plausibility limits every recorded game must respect, `None` disables a rule
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct GameRules {
+    /// Taps per second a human can sustain
+    pub max_score_per_second: Option<u64>,
+    pub min_game_time: Option<u64>,
+    pub max_game_time: Option<u64>,
+    /// Absolute cap on the score of a single game
+    pub max_score: Option<u64>,
+}
+
+/// Bounds, in seconds, on how long a session may last before `EndGame`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SessionLimits {
+    pub min_duration: u64,
+    /// Sessions older than this are expired and can no longer be ended
+    pub max_duration: u64,
+    /// Only take default game plays through sessions, refusing the
+    /// `NewGame` and `SubmitSignedGame` ones, whose `game_time` is given by
+    /// the submitter or the game server instead of measured on chain. On
+    /// unless turned off
+    #[serde(default = "sessions_only_by_default")]
+    pub sessions_only: bool,
+}
+
+fn sessions_only_by_default() -> bool {
+    true
+}
+
+impl Default for SessionLimits {
+    fn default() -> Self {
+        SessionLimits {
+            min_duration: 1,
+            max_duration: 3600,
+            sessions_only: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Session {
+    pub player: Addr,
+    pub started_at: Timestamp,
+    pub ended_at: Option<Timestamp>,
+}
+
+/// Competition window, games recorded between `start` and `end` also go to
+/// the season leaderboard
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Season {
+    pub name: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Set once the prize pool has been paid out
+    pub finalized: bool,
+}
+
+impl Season {
+    pub fn status(&self, now: Timestamp) -> SeasonStatus {
+        if now < self.start {
+            SeasonStatus::Upcoming
+        } else if now < self.end {
+            SeasonStatus::Active
+        } else {
+            SeasonStatus::Finished
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SeasonStatus {
+    Upcoming,
+    Active,
+    /// The leaderboard no longer changes and stays queryable as an archive
+    Finished,
+}
+
+/// Competition players pay to join, the entry fees make up the prize pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Tournament {
+    pub entry_fee: Coin,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub max_players: u32,
+    /// With fewer players at `start` the tournament does not run and every
+    /// entry fee can be refunded
+    pub min_players: u32,
+    pub payout_table: Vec<Decimal>,
+    /// House fee when the tournament was created
+    pub house_fee: Decimal,
+    pub players: u32,
+    pub state: TournamentState,
+}
+
+impl Tournament {
+    pub fn under_subscribed(&self) -> bool {
+        self.players < self.min_players
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentState {
+    Open,
+    Settled,
+    Cancelled,
+}
+
+/// Entry of a tournament leaderboard, which carries its tournament for the
+/// score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TournamentRankEntry {
+    pub tournament_id: u64,
+    pub score: u64,
+}
+
+/// Game or game mode registered next to the default game, with its own
+/// leaderboard, total and submitters
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RegisteredGame {
+    pub name: String,
+    pub config: GameConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct GameConfig {
+    pub rank_policy: RankPolicy,
+    pub game_rules: GameRules,
+}
+
+/// Entry of a registered game leaderboard, which carries its game for the
+/// score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GameRankEntry {
+    pub game_id: String,
+    pub score: u64,
+}
+
+/// Team of players ranked together on the club leaderboard
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Club {
+    pub name: String,
+    pub owner: Addr,
+    /// Anyone can join an open club, the others need an invite from the owner
+    pub open: bool,
+    pub max_members: u32,
+    pub members: u32,
+    pub scoring: ClubScoring,
+    /// Score on the club leaderboard, saturating at `u64::MAX`
+    pub score: u64,
+    /// How the owner distributes the club treasury
+    pub split: ClubSplit,
+}
+
+/// How the best scores of the members add up to the club score
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClubScoring {
+    Sum,
+    /// Only the `k` highest scores count
+    TopK {
+        k: u32,
+    },
+}
+
+/// How a club treasury is shared among the members when distributed
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClubSplit {
+    /// Proportional to the best score each member brings to the club
+    #[default]
+    Contribution,
+    /// Fixed weight per member, members without a weight get nothing
+    Weights { weights: Vec<(Addr, u32)> },
+}
+
+/// Distribution of a club treasury, kept as the club payout history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ClubPayout {
+    pub distributed_at: Timestamp,
+    pub shares: Vec<(Addr, Vec<Coin>)>,
+}
+
+/// Score a member brought to a club in a season, from the games played while
+/// in the club. Carries its season and club for the score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SeasonClubMember {
+    pub season_id: u64,
+    pub club_id: u64,
+    pub score: u64,
+}
+
+/// Score of a club in a season, which carries its season for the score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SeasonClubScore {
+    pub season_id: u64,
+    pub score: u64,
+}
+
+/// Best score a member brings to the club, which carries its club for the
+/// score index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ClubMember {
+    pub club_id: u64,
+    pub score: u64,
+}
+
+/// Milestone rewarded with an NFT, minted at most once per player
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Achievement {
+    pub name: String,
+    pub description: String,
+    pub rule: AchievementRule,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementRule {
+    FirstGame,
+    ScoreAtLeast {
+        score: u64,
+    },
+    GamesPlayed {
+        games: u64,
+    },
+    /// Finishing a season among the first `top` positions, awarded when the
+    /// season is finalized
+    SeasonPodium {
+        top: u32,
+    },
+    /// Playing `days` days in a row
+    Streak {
+        days: u32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PlayerAchievement {
+    pub token_id: String,
+    pub earned_at: Timestamp,
+}
+
+/// Prize paid to a player when a season is finalized
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SeasonPayout {
+    pub player: Addr,
+    pub amount: Vec<Coin>,
+    /// Amount of the CW20 reward token
+    pub token_amount: Uint128,
+}
+
+/// CW20 reward tokens credited to a player, split by how they get paid
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct TokenRewards {
+    /// Held by the contract, e.g. funded prize pools, paid with `Transfer`
+    pub transfer: Uint128,
+    /// Paid with `Mint`, which requires the contract to be a token minter
+    pub mint: Uint128,
+}
+
+/// Set while the contract is paused
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Pause {
+    pub paused_at: Timestamp,
+    pub reason: PauseReason,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseReason {
+    /// Paused with `Pause`
+    Admin,
+    /// A block went over `max_games_per_block`
+    SubmissionCeiling,
+}
+
+/// Games recorded in a block
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BlockGames {
+    pub height: u64,
+    pub games: u64,
+}
+
+/// Permission to run a group of privileged messages
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Held by the owner only, changes hands with `TransferOwnership`
+    Owner,
+    /// Configures games, seasons and tournaments
+    Operator,
+    /// Records games of the default game
+    ScoreSubmitter,
+    Pauser,
+    /// Sets fees and earnings, and sweeps unclaimed rewards
+    Treasurer,
+    /// Manages achievements and cancels tournaments
+    Moderator,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::Operator => "operator",
+            Role::ScoreSubmitter => "score_submitter",
+            Role::Pauser => "pauser",
+            Role::Treasurer => "treasurer",
+            Role::Moderator => "moderator",
+        }
+    }
+}
+
+/// Owner of the contract, allowed to run the admin messages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ownership {
+    /// `None` once ownership has been renounced
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
 }
@@ -1,7 +1,371 @@
-use super::model::Game;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use super::model::{
+    Achievement, BlockGames, Club, ClubMember, ClubPayout, Config, DailyEmission, Game,
+    GameRankEntry, LegacyGame, Ownership, Pause, PlayerAchievement, PlayerStats, RankEntry,
+    RegisteredGame, Role, Season, SeasonClubMember, SeasonClubScore, SeasonPayout, SeasonRankEntry,
+    SeasonStatus, Session, Streak, TokenRewards, Tournament, TournamentRankEntry,
+};
+use cosmwasm_std::{Addr, Coin, Empty, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
-pub const GAMES: Map<Addr, Game> = Map::new("games");
-pub const RANK: Item<Vec<(u64, Addr)>> = Item::new("rank");
+pub const ACHIEVEMENTS: Map<u64, Achievement> = Map::new("achievements");
+pub const ACHIEVEMENT_COUNT: Item<u64> = Item::new("achievement_count");
+// Games recorded in the current block, for the circuit breaker
+pub const BLOCK_GAMES: Item<BlockGames> = Item::new("block_games");
+pub const CLUB_COUNT: Item<u64> = Item::new("club_count");
+pub const CLUB_INVITES: Map<(u64, Addr), Empty> = Map::new("club_invites");
+// Distributions of each club treasury, under (club_id, payout_id)
+pub const CLUB_PAYOUTS: Map<(u64, u64), ClubPayout> = Map::new("club_payouts");
+// Funds each club holds until its owner distributes them
+pub const CLUB_TREASURIES: Map<u64, Vec<Coin>> = Map::new("club_treasuries");
+pub const CONFIG: Item<Config> = Item::new("config");
+// Tokens emitted today by all players together, overwritten by the first game
+// of a new day
+pub const EMISSION_TODAY: Item<DailyEmission> = Item::new("emission_today");
+// Every default game play, stored under (player, record_id)
+pub const GAMES: Map<(Addr, u64), Game> = Map::new("player_games");
+pub const GAME_PLAYERS: Map<u64, Addr> = Map::new("game_players");
+// Registered games are kept apart from the default game, which still uses the
+// storage layout from before the registry
+pub const DEFAULT_GAME_ID: &str = "default";
+pub const REGISTERED_GAMES: Map<&str, RegisteredGame> = Map::new("registered_games");
+pub const GAME_SUBMITTERS: Map<(&str, Addr), Empty> = Map::new("game_submitters");
+pub const GAME_TOTALS: Map<&str, u64> = Map::new("game_totals");
+// Score of the last game of each player in each registered game
+pub const GAME_LAST_SCORES: Map<(&str, Addr), u64> = Map::new("game_last_scores");
+// Last game of each player up to v0.1.0, only read when migrating
+pub const LEGACY_GAMES: Map<Addr, LegacyGame> = Map::new("games");
+// Ranking layout up to v0.1.0, only read when migrating
+pub const LEGACY_RANK: Item<Vec<(u64, Addr)>> = Item::new("rank");
+pub const NONCES: Map<(Addr, u64), Empty> = Map::new("nonces");
+pub const OWNERSHIP: Item<Ownership> = Item::new("ownership");
+// Only saved while the contract is paused
+pub const PAUSE: Item<Pause> = Item::new("pause");
+// Rewards credited to each player and not claimed yet
+pub const PENDING_REWARDS: Map<Addr, Vec<Coin>> = Map::new("pending_rewards");
+pub const PENDING_TOKEN_REWARDS: Map<Addr, TokenRewards> = Map::new("pending_token_rewards");
+pub const REWARDS_CREDITED_AT: Map<Addr, Timestamp> = Map::new("rewards_credited_at");
+// Same as EMISSION_TODAY for each player
+pub const PLAYER_EMISSION: Map<Addr, DailyEmission> = Map::new("player_emission");
+pub const PLAYER_STATS: Map<Addr, PlayerStats> = Map::new("player_stats");
+// Achievements each player earned, under (player, achievement_id)
+pub const PLAYER_ACHIEVEMENTS: Map<(Addr, u64), PlayerAchievement> =
+    Map::new("player_achievements");
+// Club of each player, a player is a member of at most one club
+pub const PLAYER_CLUBS: Map<Addr, u64> = Map::new("player_clubs");
+pub const PLAYER_STREAKS: Map<Addr, Streak> = Map::new("player_streaks");
+// Number of players on the leaderboard
+pub const RANKED_PLAYERS: Item<u64> = Item::new("ranked_players");
+// Referrer of each player, set once before the first game
+pub const REFERRERS: Map<Addr, Addr> = Map::new("referrers");
+// Tokens each referee earned its referrer, under (referrer, referee)
+pub const REFERRALS: Map<(Addr, Addr), Uint128> = Map::new("referrals");
+// Tokens each referrer earned from all of its referees
+pub const REFERRAL_EARNINGS: Map<Addr, Uint128> = Map::new("referral_earnings");
+pub const SEASONS: Map<u64, Season> = Map::new("seasons");
+pub const SEASON_COUNT: Item<u64> = Item::new("season_count");
+// Funds to be paid out when each season is finalized, what is left after
+// finalizing stays recorded here
+pub const SEASON_POOLS: Map<u64, Vec<Coin>> = Map::new("season_pools");
+// Funds moved to the treasury of the top club when each season is finalized
+pub const SEASON_CLUB_POOLS: Map<u64, Vec<Coin>> = Map::new("season_club_pools");
+// Club whose treasury got the club prize of each finalized season
+pub const SEASON_CLUB_WINNERS: Map<u64, u64> = Map::new("season_club_winners");
+// Reward tokens to be paid out when each season is finalized
+pub const SEASON_TOKEN_POOLS: Map<u64, Uint128> = Map::new("season_token_pools");
+// Prizes paid by finalized seasons, under (season_id, position)
+pub const SEASON_PAYOUTS: Map<(u64, u64), SeasonPayout> = Map::new("season_payouts");
+// Games recorded in each season
+pub const SEASON_TOTALS: Map<u64, u64> = Map::new("season_totals");
+pub const SESSIONS: Map<u64, Session> = Map::new("sessions");
+pub const SESSION_COUNT: Item<u64> = Item::new("session_count");
+pub const SUBMITTERS: Map<Addr, Empty> = Map::new("submitters");
+pub const OPERATORS: Map<Addr, Empty> = Map::new("operators");
+pub const PAUSERS: Map<Addr, Empty> = Map::new("pausers");
+pub const TREASURERS: Map<Addr, Empty> = Map::new("treasurers");
+pub const MODERATORS: Map<Addr, Empty> = Map::new("moderators");
 pub const TOTAL: Item<u64> = Item::new("total");
+pub const TOURNAMENTS: Map<u64, Tournament> = Map::new("tournaments");
+pub const TOURNAMENT_COUNT: Item<u64> = Item::new("tournament_count");
+pub const TOURNAMENT_PLAYERS: Map<(u64, Addr), Empty> = Map::new("tournament_players");
+// Tournaments each player joined, under (player, end, tournament_id) so that
+// recording a game only reads the ones that have not ended
+pub const PLAYER_TOURNAMENTS: Map<(Addr, u64, u64), Empty> = Map::new("player_tournaments");
+
+pub struct RankIndexes<'a> {
+    pub score: MultiIndex<'a, u64, RankEntry, Addr>,
+}
+
+impl<'a> IndexList<RankEntry> for RankIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RankEntry>> + '_> {
+        let v: Vec<&dyn Index<RankEntry>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+pub struct SeasonRankIndexes<'a> {
+    pub score: MultiIndex<'a, (u64, u64), SeasonRankEntry, (u64, Addr)>,
+}
+
+impl<'a> IndexList<SeasonRankEntry> for SeasonRankIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SeasonRankEntry>> + '_> {
+        let v: Vec<&dyn Index<SeasonRankEntry>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Players on the leaderboard by score prefix, under (level, score >> 4 * level).
+// Counting the players above a score reads at most 15 siblings per level,
+// however many players are ranked
+pub const SCORE_COUNTS: Map<(u8, u64), u64> = Map::new("score_counts");
+const SCORE_LEVELS: u8 = 16;
+
+// Moves a player from `old` to `new` in SCORE_COUNTS, `None` being off the
+// leaderboard. Levels above the first shared prefix are left untouched
+pub fn update_score_counts(
+    storage: &mut dyn Storage,
+    old: Option<u64>,
+    new: Option<u64>,
+) -> StdResult<()> {
+    for level in 0..SCORE_LEVELS {
+        let shift = 4 * u32::from(level);
+        let old_node = old.map(|score| score >> shift);
+        let new_node = new.map(|score| score >> shift);
+        if old_node == new_node {
+            break;
+        }
+        if let Some(node) = old_node {
+            let count = SCORE_COUNTS.load(storage, (level, node))? - 1;
+            match count {
+                0 => SCORE_COUNTS.remove(storage, (level, node)),
+                count => SCORE_COUNTS.save(storage, (level, node), &count)?,
+            }
+        }
+        if let Some(node) = new_node {
+            SCORE_COUNTS.update(storage, (level, node), |count| -> StdResult<_> {
+                Ok(count.unwrap_or_default() + 1)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// Players on the leaderboard with a higher score
+pub fn players_above(storage: &dyn Storage, score: u64) -> StdResult<u64> {
+    let mut above = 0;
+    for level in 0..SCORE_LEVELS {
+        let node = score >> (4 * u32::from(level));
+        let last_sibling = node | 0xf;
+        if node == last_sibling {
+            continue;
+        }
+        for item in SCORE_COUNTS.prefix(level).range(
+            storage,
+            Some(Bound::exclusive(node)),
+            Some(Bound::inclusive(last_sibling)),
+            Order::Ascending,
+        ) {
+            above += item?.1;
+        }
+    }
+    Ok(above)
+}
+
+// Leaderboard keyed by player, ordered through the score index
+pub fn rank<'a>() -> IndexedMap<'a, Addr, RankEntry, RankIndexes<'a>> {
+    let indexes = RankIndexes {
+        score: MultiIndex::new(
+            |_pk, entry| entry.score,
+            "rank_entries",
+            "rank_entries__score",
+        ),
+    };
+    IndexedMap::new("rank_entries", indexes)
+}
+
+// Leaderboard of each season keyed by (season_id, player), ordered through the
+// (season_id, score) index
+pub fn season_rank<'a>() -> IndexedMap<'a, (u64, Addr), SeasonRankEntry, SeasonRankIndexes<'a>> {
+    let indexes = SeasonRankIndexes {
+        score: MultiIndex::new(
+            |_pk, entry| (entry.season_id, entry.score),
+            "season_rank_entries",
+            "season_rank_entries__score",
+        ),
+    };
+    IndexedMap::new("season_rank_entries", indexes)
+}
+
+// Seasons cannot overlap and are created in chronological order, so the active
+// season is the last one that already started, if it has not ended yet
+pub fn active_season(storage: &dyn Storage, now: Timestamp) -> StdResult<Option<u64>> {
+    for item in SEASONS.range(storage, None, None, Order::Descending) {
+        let (season_id, season) = item?;
+        match season.status(now) {
+            SeasonStatus::Upcoming => continue,
+            SeasonStatus::Active => return Ok(Some(season_id)),
+            SeasonStatus::Finished => return Ok(None),
+        }
+    }
+    Ok(None)
+}
+
+// Accounts granted each role. The owner is kept in OWNERSHIP, and score
+// submitters are the submitters of the default game
+pub fn role_members(role: Role) -> Option<Map<'static, Addr, Empty>> {
+    match role {
+        Role::Owner => None,
+        Role::Operator => Some(OPERATORS),
+        Role::ScoreSubmitter => Some(SUBMITTERS),
+        Role::Pauser => Some(PAUSERS),
+        Role::Treasurer => Some(TREASURERS),
+        Role::Moderator => Some(MODERATORS),
+    }
+}
+
+// `None` and "default" both name the default game
+pub fn registered_game(game_id: Option<String>) -> Option<String> {
+    game_id.filter(|game_id| game_id != DEFAULT_GAME_ID)
+}
+
+pub struct GameRankIndexes<'a> {
+    pub score: MultiIndex<'a, (String, u64), GameRankEntry, (String, Addr)>,
+}
+
+impl<'a> IndexList<GameRankEntry> for GameRankIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<GameRankEntry>> + '_> {
+        let v: Vec<&dyn Index<GameRankEntry>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Leaderboard of each registered game keyed by (game_id, player), ordered
+// through the (game_id, score) index
+pub fn game_rank<'a>() -> IndexedMap<'a, (&'a str, Addr), GameRankEntry, GameRankIndexes<'a>> {
+    let indexes = GameRankIndexes {
+        score: MultiIndex::new(
+            |_pk, entry| (entry.game_id.clone(), entry.score),
+            "game_rank_entries",
+            "game_rank_entries__score",
+        ),
+    };
+    IndexedMap::new("game_rank_entries", indexes)
+}
+
+pub struct ClubIndexes<'a> {
+    pub score: MultiIndex<'a, u64, Club, u64>,
+}
+
+impl<'a> IndexList<Club> for ClubIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Club>> + '_> {
+        let v: Vec<&dyn Index<Club>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Clubs keyed by id, the score index is the club leaderboard
+pub fn clubs<'a>() -> IndexedMap<'a, u64, Club, ClubIndexes<'a>> {
+    let indexes = ClubIndexes {
+        score: MultiIndex::new(|_pk, club| club.score, "clubs", "clubs__score"),
+    };
+    IndexedMap::new("clubs", indexes)
+}
+
+pub struct ClubMemberIndexes<'a> {
+    pub score: MultiIndex<'a, (u64, u64), ClubMember, (u64, Addr)>,
+}
+
+impl<'a> IndexList<ClubMember> for ClubMemberIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ClubMember>> + '_> {
+        let v: Vec<&dyn Index<ClubMember>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+pub struct SeasonClubMemberIndexes<'a> {
+    pub score: MultiIndex<'a, (u64, u64, u64), SeasonClubMember, (u64, u64, Addr)>,
+}
+
+impl<'a> IndexList<SeasonClubMember> for SeasonClubMemberIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SeasonClubMember>> + '_> {
+        let v: Vec<&dyn Index<SeasonClubMember>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Score each member brought to its club in each season, keyed by
+// (season_id, club_id, player) and ordered through the
+// (season_id, club_id, score) index
+pub fn season_club_members<'a>(
+) -> IndexedMap<'a, (u64, u64, Addr), SeasonClubMember, SeasonClubMemberIndexes<'a>> {
+    let indexes = SeasonClubMemberIndexes {
+        score: MultiIndex::new(
+            |_pk, member| (member.season_id, member.club_id, member.score),
+            "season_club_members",
+            "season_club_members__score",
+        ),
+    };
+    IndexedMap::new("season_club_members", indexes)
+}
+
+pub struct SeasonClubScoreIndexes<'a> {
+    pub score: MultiIndex<'a, (u64, u64), SeasonClubScore, (u64, u64)>,
+}
+
+impl<'a> IndexList<SeasonClubScore> for SeasonClubScoreIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SeasonClubScore>> + '_> {
+        let v: Vec<&dyn Index<SeasonClubScore>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Club leaderboard of each season keyed by (season_id, club_id), ordered
+// through the (season_id, score) index
+pub fn season_club_scores<'a>(
+) -> IndexedMap<'a, (u64, u64), SeasonClubScore, SeasonClubScoreIndexes<'a>> {
+    let indexes = SeasonClubScoreIndexes {
+        score: MultiIndex::new(
+            |_pk, club| (club.season_id, club.score),
+            "season_club_scores",
+            "season_club_scores__score",
+        ),
+    };
+    IndexedMap::new("season_club_scores", indexes)
+}
+
+// Members of each club keyed by (club_id, player), ordered through the
+// (club_id, score) index
+pub fn club_members<'a>() -> IndexedMap<'a, (u64, Addr), ClubMember, ClubMemberIndexes<'a>> {
+    let indexes = ClubMemberIndexes {
+        score: MultiIndex::new(
+            |_pk, member| (member.club_id, member.score),
+            "club_members",
+            "club_members__score",
+        ),
+    };
+    IndexedMap::new("club_members", indexes)
+}
+
+pub struct TournamentRankIndexes<'a> {
+    pub score: MultiIndex<'a, (u64, u64), TournamentRankEntry, (u64, Addr)>,
+}
+
+impl<'a> IndexList<TournamentRankEntry> for TournamentRankIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TournamentRankEntry>> + '_> {
+        let v: Vec<&dyn Index<TournamentRankEntry>> = vec![&self.score];
+        Box::new(v.into_iter())
+    }
+}
+
+// Leaderboard of each tournament keyed by (tournament_id, player), ordered
+// through the (tournament_id, score) index
+pub fn tournament_rank<'a>(
+) -> IndexedMap<'a, (u64, Addr), TournamentRankEntry, TournamentRankIndexes<'a>> {
+    let indexes = TournamentRankIndexes {
+        score: MultiIndex::new(
+            |_pk, entry| (entry.tournament_id, entry.score),
+            "tournament_rank_entries",
+            "tournament_rank_entries__score",
+        ),
+    };
+    IndexedMap::new("tournament_rank_entries", indexes)
+}
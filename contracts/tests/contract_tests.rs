@@ -1,19 +1,55 @@
 #[cfg(test)]
 mod tests {
-    use cw_counter::contract::{execute, instantiate, query};
+    use cw_counter::contract::{execute, instantiate, migrate, query, CONTRACT_VERSION};
+    use cw_counter::error::ContractError;
     use cw_counter::msg::execute::ExecuteMsg;
+    use cw_counter::msg::execute::ScoreAttestation;
     use cw_counter::msg::instantiate::InstantiateMsg;
+    use cw_counter::msg::migrate::MigrateMsg;
     use cw_counter::msg::query::QueryMsg;
-    use cw_counter::msg::response::{GetRankResponse, GetScoreByPlayerResponse, GetTotalResponse};
+    use cw_counter::msg::response::{
+        GetClubRankResponse, GetClubResponse, GetConfigResponse, GetEmissionStatusResponse,
+        GetGameResponse, GetOwnershipResponse, GetPauseResponse, GetPendingRewardsResponse,
+        GetPlayerClubResponse, GetPlayerHistoryResponse, GetPlayerRankResponse,
+        GetPlayerStatsResponse, GetRankAroundResponse, GetRankResponse, GetRefereesResponse,
+        GetReferralResponse, GetScoreByPlayerResponse, GetSeasonResponse, GetSessionResponse,
+        GetStreakResponse, GetSubmittersResponse, GetTotalResponse, HasRoleResponse,
+        ListGamesResponse, ListRoleMembersResponse, ListSeasonsResponse,
+    };
+    use cw_counter::state::model::{
+        ClubScoring, EmissionSchedule, GameConfig, GameRules, LegacyGame, PauseReason, RankPolicy,
+        Role, SeasonStatus, ServerKey, SessionLimits, SignatureScheme, StreakConfig,
+    };
+    use cw_counter::state::storage::{LEGACY_GAMES, LEGACY_RANK, TOTAL};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_json, Addr};
+    use cosmwasm_std::{
+        coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, ContractInfoResponse,
+        ContractResult, CosmosMsg, Decimal, Deps, DepsMut, Env, OwnedDeps, Response, SystemResult,
+        Uint128, WasmQuery,
+    };
+    use cw2::{get_contract_version, set_contract_version};
+    use cw_utils::Expiration;
+    use ed25519_zebra::{SigningKey, VerificationKey};
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+
+    // Lets NewGame record default game plays, sessions are required otherwise
+    fn trusted_game_time() -> Option<SessionLimits> {
+        Some(SessionLimits {
+            sessions_only: false,
+            ..SessionLimits::default()
+        })
+    }
 
     #[test]
     fn proper_initialization() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let info = mock_info("creator", &coins(1000, "earth"));
 
         // Inicialização deve ser bem-sucedida
@@ -21,12 +57,27 @@ mod tests {
         assert_eq!(0, res.messages.len());
 
         // Verificar estado inicial - total deve ser 0
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTotal {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetTotal { game_id: None },
+        )
+        .unwrap();
         let value: GetTotalResponse = from_json(&res).unwrap();
         assert_eq!(0, value.total);
 
         // Verificar rank inicial - deve estar vazio
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRank {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
         let value: GetRankResponse = from_json(&res).unwrap();
         assert_eq!(0, value.rank.len());
     }
@@ -35,7 +86,11 @@ mod tests {
     fn single_player_game() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let info = mock_info("creator", &coins(2, "token"));
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -46,6 +101,7 @@ mod tests {
             player: player.clone(),
             score: 100,
             game_time: 60,
+            game_id: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -55,6 +111,7 @@ mod tests {
             mock_env(),
             QueryMsg::GetScoreByPlayer {
                 player: player.clone(),
+                game_id: None,
             },
         )
         .unwrap();
@@ -62,12 +119,27 @@ mod tests {
         assert_eq!(100, value.score);
 
         // Verificar total de jogos
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTotal {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetTotal { game_id: None },
+        )
+        .unwrap();
         let value: GetTotalResponse = from_json(&res).unwrap();
         assert_eq!(1, value.total);
 
         // Verificar rank
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRank {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
         let value: GetRankResponse = from_json(&res).unwrap();
         assert_eq!(1, value.rank.len());
         assert_eq!((100, player), value.rank[0]);
@@ -77,7 +149,11 @@ mod tests {
     fn multiple_players_games() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let info = mock_info("creator", &coins(2, "token"));
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -95,12 +171,18 @@ mod tests {
                 player: player.clone(),
                 score: *score,
                 game_time: *game_time,
+                game_id: None,
             };
             let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
         }
 
         // Verificar total de jogos
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTotal {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetTotal { game_id: None },
+        )
+        .unwrap();
         let value: GetTotalResponse = from_json(&res).unwrap();
         assert_eq!(4, value.total);
 
@@ -111,6 +193,7 @@ mod tests {
                 mock_env(),
                 QueryMsg::GetScoreByPlayer {
                     player: player.clone(),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -119,7 +202,17 @@ mod tests {
         }
 
         // Verificar ranking (deve estar ordenado por score decrescente)
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRank {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
         let value: GetRankResponse = from_json(&res).unwrap();
         assert_eq!(4, value.rank.len());
 
@@ -134,7 +227,11 @@ mod tests {
     fn player_updates_score() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let info = mock_info("creator", &coins(2, "token"));
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -146,6 +243,7 @@ mod tests {
             player: player.clone(),
             score: 100,
             game_time: 60,
+            game_id: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -155,6 +253,7 @@ mod tests {
             player: player.clone(),
             score: 250,
             game_time: 45,
+            game_id: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -164,6 +263,7 @@ mod tests {
             mock_env(),
             QueryMsg::GetScoreByPlayer {
                 player: player.clone(),
+                game_id: None,
             },
         )
         .unwrap();
@@ -171,23 +271,41 @@ mod tests {
         assert_eq!(250, value.score);
 
         // Verificar que o total de jogos aumentou
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTotal {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetTotal { game_id: None },
+        )
+        .unwrap();
         let value: GetTotalResponse = from_json(&res).unwrap();
         assert_eq!(2, value.total);
 
         // Verificar ranking atualizado
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRank {}).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
         let value: GetRankResponse = from_json(&res).unwrap();
-        assert_eq!(2, value.rank.len());
-        assert_eq!((250, player.clone()), value.rank[0]);
-        assert_eq!((100, player), value.rank[1]);
+        assert_eq!(1, value.rank.len());
+        assert_eq!((250, player), value.rank[0]);
     }
 
     #[test]
     fn query_nonexistent_player() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let info = mock_info("creator", &coins(2, "token"));
         let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -197,10 +315,2267 @@ mod tests {
             mock_env(),
             QueryMsg::GetScoreByPlayer {
                 player: Addr::unchecked("nonexistent"),
+                game_id: None,
             },
         );
 
         // Deve retornar erro
         assert!(res.is_err());
     }
+
+    #[test]
+    fn rank_pagination() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        let info = mock_info("creator", &coins(2, "token"));
+        let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        for (player, score) in [
+            ("alice", 150),
+            ("bob", 200),
+            ("charlie", 75),
+            ("diana", 300),
+        ] {
+            let info = mock_info("anyone", &[]);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        // Primeira página com os dois melhores
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+        let page: GetRankResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![
+                (300, Addr::unchecked("diana")),
+                (200, Addr::unchecked("bob"))
+            ],
+            page.rank
+        );
+
+        // Segunda página continua a partir da última entrada
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: page.rank.last().cloned(),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+        let page: GetRankResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![
+                (150, Addr::unchecked("alice")),
+                (75, Addr::unchecked("charlie"))
+            ],
+            page.rank
+        );
+
+        // Não há mais entradas
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: page.rank.last().cloned(),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+        let page: GetRankResponse = from_json(&res).unwrap();
+        assert!(page.rank.is_empty());
+    }
+
+    fn rank_after_games(rank_policy: Option<RankPolicy>, scores: &[u64]) -> GetRankResponse {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            rank_policy,
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        for score in scores {
+            let info = mock_info("anyone", &[]);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked("player1"),
+                score: *score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    }
+
+    #[test]
+    fn rank_policies() {
+        let player = Addr::unchecked("player1");
+        let scores = [100, 250, 80];
+
+        // Padrão: melhor score
+        let value = rank_after_games(None, &scores);
+        assert_eq!(vec![(250, player.clone())], value.rank);
+
+        // Último score
+        let value = rank_after_games(Some(RankPolicy::Latest), &scores);
+        assert_eq!(vec![(80, player.clone())], value.rank);
+
+        // Soma de todos os scores
+        let value = rank_after_games(Some(RankPolicy::Cumulative), &scores);
+        assert_eq!(vec![(430, player.clone())], value.rank);
+
+        // Soma que estoura u64 é rejeitada
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["anyone".to_string()]),
+            rank_policy: Some(RankPolicy::Cumulative),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        let msg = ExecuteMsg::NewGame {
+            player: player.clone(),
+            score: u64::MAX,
+            game_time: 60,
+            game_id: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+        let msg = ExecuteMsg::NewGame {
+            player,
+            score: 1,
+            game_time: 60,
+            game_id: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Overflow(_)));
+    }
+
+    #[test]
+    fn only_submitters_record_games() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            owner: Some("admin".to_string()),
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Configuração salva com o owner informado
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let value: GetConfigResponse = from_json(&res).unwrap();
+        assert_eq!(RankPolicy::Best, value.rank_policy);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwnership {}).unwrap();
+        let value: GetOwnershipResponse = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("admin")), value.owner);
+
+        let new_game = ExecuteMsg::NewGame {
+            player: Addr::unchecked("player1"),
+            score: 100,
+            game_time: 60,
+            game_id: None,
+        };
+
+        // O próprio jogador não pode registrar seu score
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("player1", &[]),
+            new_game.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // O backend autorizado pode
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            new_game.clone(),
+        )
+        .unwrap();
+
+        // Somente o admin gerencia os submitters
+        let add = ExecuteMsg::AddSubmitter {
+            address: "backend2".to_string(),
+            game_id: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            add.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), add).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetSubmitters {
+                start_after: None,
+                limit: None,
+                game_id: None,
+            },
+        )
+        .unwrap();
+        let value: GetSubmittersResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![Addr::unchecked("backend"), Addr::unchecked("backend2")],
+            value.submitters
+        );
+
+        // Submitter removido perde o acesso
+        let remove = ExecuteMsg::RemoveSubmitter {
+            address: "backend".to_string(),
+            game_id: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), remove).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            new_game,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+
+    fn attestation(env: &Env, player: &str, score: u64, nonce: u64) -> ScoreAttestation {
+        ScoreAttestation {
+            player: Addr::unchecked(player),
+            score,
+            game_time: 60,
+            nonce,
+            chain_id: env.block.chain_id.clone(),
+            contract_addr: env.contract.address.clone(),
+        }
+    }
+
+    fn submit_signed(attestation: &ScoreAttestation, signature: Vec<u8>) -> ExecuteMsg {
+        ExecuteMsg::SubmitSignedGame {
+            score: attestation.score,
+            game_time: attestation.game_time,
+            nonce: attestation.nonce,
+            signature: Binary::from(signature),
+        }
+    }
+
+    #[test]
+    fn signed_game_ed25519() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let server = SigningKey::from([7u8; 32]);
+        let pubkey: [u8; 32] = VerificationKey::from(&server).into();
+
+        let msg = InstantiateMsg {
+            server_key: Some(ServerKey {
+                scheme: SignatureScheme::Ed25519,
+                pubkey: Binary::from(pubkey.to_vec()),
+            }),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        // O jogador envia o jogo assinado pelo servidor
+        let game = attestation(&env, "player1", 120, 1);
+        let signature: [u8; 64] = server.sign(&game.hash().unwrap()).into();
+        let msg = submit_signed(&game, signature.to_vec());
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player1", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetScoreByPlayer {
+                player: Addr::unchecked("player1"),
+                game_id: None,
+            },
+        )
+        .unwrap();
+        let value: GetScoreByPlayerResponse = from_json(&res).unwrap();
+        assert_eq!(120, value.score);
+
+        // O mesmo nonce não pode ser reutilizado
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player1", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NonceAlreadyUsed { nonce: 1 }));
+
+        // A assinatura é vinculada ao jogador
+        let err = execute(deps.as_mut(), env.clone(), mock_info("player2", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+
+        // Score adulterado invalida a assinatura
+        let game = attestation(&env, "player1", 120, 2);
+        let signature: [u8; 64] = server.sign(&game.hash().unwrap()).into();
+        let msg = ExecuteMsg::SubmitSignedGame {
+            score: 9999,
+            game_time: game.game_time,
+            nonce: game.nonce,
+            signature: Binary::from(signature.to_vec()),
+        };
+        let err = execute(deps.as_mut(), env, mock_info("player1", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+    }
+
+    #[test]
+    fn signed_game_secp256k1() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let server = k256::ecdsa::SigningKey::from_bytes(&[9u8; 32].into()).unwrap();
+        let pubkey = server.verifying_key().to_encoded_point(true);
+
+        // Sem chave configurada o envio assinado é recusado
+        let msg = InstantiateMsg {
+            owner: Some("admin".to_string()),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let game = attestation(&env, "player1", 300, 1);
+        let signature: k256::ecdsa::Signature = server.sign_prehash(&game.hash().unwrap()).unwrap();
+        let msg = submit_signed(&game, signature.to_bytes().to_vec());
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player1", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ServerKeyNotSet {}));
+
+        // Admin configura a chave do servidor
+        let set_key = ExecuteMsg::SetServerKey {
+            server_key: Some(ServerKey {
+                scheme: SignatureScheme::Secp256k1,
+                pubkey: Binary::from(pubkey.as_bytes()),
+            }),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player1", &[]),
+            set_key.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), set_key).unwrap();
+
+        execute(deps.as_mut(), env.clone(), mock_info("player1", &[]), msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: GetRankResponse = from_json(&res).unwrap();
+        assert_eq!(vec![(300, Addr::unchecked("player1"))], value.rank);
+    }
+
+    #[test]
+    fn migrate_legacy_rank() {
+        let mut deps = mock_dependencies();
+
+        // Estado gravado pela v0.1.0
+        let storage = deps.as_mut().storage;
+        set_contract_version(storage, "crates.io:increment", "0.1.0").unwrap();
+        TOTAL.save(storage, &3).unwrap();
+        LEGACY_RANK
+            .save(
+                storage,
+                &vec![
+                    (250, Addr::unchecked("alice")),
+                    (200, Addr::unchecked("bob")),
+                    (100, Addr::unchecked("alice")),
+                ],
+            )
+            .unwrap();
+        for (player, score) in [("alice", 100), ("bob", 200), ("creator", 0)] {
+            let game = LegacyGame {
+                score,
+                game_time: if score == 0 { 0 } else { 60 },
+            };
+            LEGACY_GAMES
+                .save(storage, Addr::unchecked(player), &game)
+                .unwrap();
+        }
+
+        // O admin do contrato na chain passa a ser o owner
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { .. } => {
+                let mut info = ContractInfoResponse::default();
+                info.creator = "creator".to_string();
+                info.admin = Some("admin".to_string());
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&info).unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        });
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            vec![("from_version", "0.1.0"), ("to_version", CONTRACT_VERSION)],
+            res.attributes[1..]
+                .iter()
+                .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+                .collect::<Vec<_>>()
+        );
+
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(CONTRACT_VERSION, version.version);
+        assert!(LEGACY_RANK
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwnership {}).unwrap();
+        let value: GetOwnershipResponse = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("admin")), value.owner);
+
+        // Contratos migrados continuam aceitando NewGame
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let value: GetConfigResponse = from_json(&res).unwrap();
+        assert!(!value.session_limits.sessions_only);
+
+        // Uma entrada por jogador, com o melhor score
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: GetRankResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![
+                (250, Addr::unchecked("alice")),
+                (200, Addr::unchecked("bob"))
+            ],
+            value.rank
+        );
+
+        // Jogos e total continuam disponíveis
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetTotal { game_id: None },
+        )
+        .unwrap();
+        let value: GetTotalResponse = from_json(&res).unwrap();
+        assert_eq!(3, value.total);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetScoreByPlayer {
+                player: Addr::unchecked("alice"),
+                game_id: None,
+            },
+        )
+        .unwrap();
+        let value: GetScoreByPlayerResponse = from_json(&res).unwrap();
+        assert_eq!(100, value.score);
+
+        // O jogo zerado do instanciador não vira histórico
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerHistory {
+                player: Addr::unchecked("creator"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: GetPlayerHistoryResponse = from_json(&res).unwrap();
+        assert!(value.games.is_empty());
+        assert!(LEGACY_GAMES.is_empty(deps.as_ref().storage));
+
+        // Estatísticas reconstruídas a partir do ranking antigo
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerStats {
+                player: Addr::unchecked("alice"),
+            },
+        )
+        .unwrap();
+        let value: GetPlayerStatsResponse = from_json(&res).unwrap();
+        assert_eq!(2, value.games_played);
+        assert_eq!(250, value.best_score);
+        assert_eq!(100, value.last_score);
+        assert_eq!(175, value.average_score);
+        assert_eq!(1, value.rank);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerRank {
+                player: Addr::unchecked("bob"),
+            },
+        )
+        .unwrap();
+        let value: GetPlayerRankResponse = from_json(&res).unwrap();
+        assert_eq!(2, value.position);
+        assert_eq!(2, value.total_players);
+    }
+
+    #[test]
+    fn migrate_rejects_other_contract_and_downgrade() {
+        let mut deps = mock_dependencies();
+
+        set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::WrongContract { .. }));
+
+        set_contract_version(deps.as_mut().storage, "crates.io:increment", "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::CannotDowngrade { .. }));
+    }
+
+    #[test]
+    fn two_step_ownership_transfer() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        // Somente o owner propõe a transferência
+        let transfer = ExecuteMsg::TransferOwnership {
+            new_owner: "ops".to_string(),
+            expiry: Some(Expiration::AtHeight(env.block.height + 10)),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            transfer.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            transfer,
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetOwnership {}).unwrap();
+        let value: GetOwnershipResponse = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("creator")), value.owner);
+        assert_eq!(Some(Addr::unchecked("ops")), value.pending_owner);
+
+        // Somente o owner pendente aceita, e antes de expirar
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        env.block.height += 10;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::TransferExpired {}));
+
+        // Nova proposta sem expiração
+        let transfer = ExecuteMsg::TransferOwnership {
+            new_owner: "ops".to_string(),
+            expiry: None,
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            transfer,
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetOwnership {}).unwrap();
+        let value: GetOwnershipResponse = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("ops")), value.owner);
+        assert_eq!(None, value.pending_owner);
+
+        // O owner anterior perde o acesso às mensagens de admin
+        let add = ExecuteMsg::AddSubmitter {
+            address: "backend".to_string(),
+            game_id: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            add.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            add.clone(),
+        )
+        .unwrap();
+
+        // Depois de renunciar ninguém mais administra o contrato
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingTransfer {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ops", &[]),
+            ExecuteMsg::RenounceOwnership {},
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::GetOwnership {}).unwrap();
+        let value: GetOwnershipResponse = from_json(&res).unwrap();
+        assert_eq!(None, value.owner);
+        let err = execute(deps.as_mut(), env, mock_info("ops", &[]), add).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn session_game_time_is_measured_on_chain() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: Some(SessionLimits {
+                min_duration: 10,
+                max_duration: 120,
+                ..SessionLimits::default()
+            }),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        // Por padrão só sessões registram partidas, o game_time informado
+        // por quem envia é recusado
+        let new_game = || ExecuteMsg::NewGame {
+            player: Addr::unchecked("player1"),
+            score: 150,
+            game_time: 45,
+            game_id: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            new_game(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SessionRequired {}));
+        let msg = ExecuteMsg::SubmitSignedGame {
+            score: 150,
+            game_time: 45,
+            nonce: 1,
+            signature: Binary::default(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("player1", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::SessionRequired {}));
+
+        // O jogador abre duas sessões
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("player1", &[]),
+                ExecuteMsg::StartGame {},
+            )
+            .unwrap();
+        }
+        let started_at = env.block.time;
+
+        let end = |session_id| ExecuteMsg::EndGame {
+            session_id,
+            score: 150,
+        };
+
+        // Somente o backend encerra a sessão
+        env.block.time = started_at.plus_seconds(45);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player1", &[]),
+            end(1),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // Sessão curta demais
+        env.block.time = started_at.plus_seconds(5);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            end(1),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::SessionTooShort { min_duration: 10 }
+        ));
+
+        // O tempo de jogo vem do bloco
+        env.block.time = started_at.plus_seconds(45);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            end(1),
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "game_time" && attr.value == "45"));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetSession { session_id: 1 },
+        )
+        .unwrap();
+        let value: GetSessionResponse = from_json(&res).unwrap();
+        assert_eq!(Addr::unchecked("player1"), value.player);
+        assert_eq!(Some(env.block.time), value.ended_at);
+
+        // Sessão já encerrada
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            end(1),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::SessionClosed { session_id: 1 }
+        ));
+
+        // Sessão expirada
+        env.block.time = started_at.plus_seconds(121);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            end(2),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::SessionExpired { max_duration: 120 }
+        ));
+
+        // Owner pode ajustar os limites e voltar a confiar no game_time
+        // informado pelos submitters
+        let msg = ExecuteMsg::SetSessionLimits {
+            session_limits: SessionLimits {
+                min_duration: 10,
+                max_duration: 300,
+                sessions_only: false,
+            },
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("backend", &[]),
+            new_game(),
+        )
+        .unwrap();
+        execute(deps.as_mut(), env, mock_info("backend", &[]), end(2)).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetScoreByPlayer {
+                player: Addr::unchecked("player1"),
+                game_id: None,
+            },
+        )
+        .unwrap();
+        let value: GetScoreByPlayerResponse = from_json(&res).unwrap();
+        assert_eq!(150, value.score);
+    }
+
+    #[test]
+    fn game_rules_reject_implausible_scores() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            game_rules: Some(GameRules {
+                max_score_per_second: Some(10),
+                min_game_time: Some(5),
+                max_game_time: Some(120),
+                max_score: Some(1000),
+            }),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let mut play = |score, game_time| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked("player1"),
+                score,
+                game_time,
+                game_id: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("backend", &[]), msg)
+        };
+
+        // 10 taps por segundo durante 30 segundos
+        play(300, 30).unwrap();
+        let err = play(301, 30).unwrap_err();
+        assert!(matches!(err, ContractError::ScoreTooHigh { max: 300 }));
+
+        // Teto absoluto vale mesmo para partidas longas
+        let err = play(1001, 120).unwrap_err();
+        assert!(matches!(err, ContractError::ScoreTooHigh { max: 1000 }));
+
+        // Duração fora dos limites
+        let err = play(10, 4).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidGameTime { min: 5, max: 120 }
+        ));
+        let err = play(10, 121).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidGameTime { min: 5, max: 120 }
+        ));
+
+        // Owner ajusta os limites sem migração
+        let msg = ExecuteMsg::SetGameRules {
+            game_rules: GameRules {
+                max_score_per_second: Some(20),
+                ..GameRules::default()
+            },
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::NewGame {
+            player: Addr::unchecked("player1"),
+            score: 600,
+            game_time: 30,
+            game_id: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("backend", &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let value: GetConfigResponse = from_json(&res).unwrap();
+        assert_eq!(Some(20), value.game_rules.max_score_per_second);
+        assert_eq!(None, value.game_rules.max_score);
+
+        // Limites inconsistentes são recusados
+        let msg = ExecuteMsg::SetGameRules {
+            game_rules: GameRules {
+                min_game_time: Some(60),
+                max_game_time: Some(30),
+                ..GameRules::default()
+            },
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidGameRules {}));
+    }
+
+    #[test]
+    fn player_history_keeps_every_game() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let start = env.block.time;
+        for (i, (player, score)) in [("alice", 100), ("bob", 90), ("alice", 250), ("alice", 80)]
+            .into_iter()
+            .enumerate()
+        {
+            env.block.time = start.plus_seconds(i as u64 * 60);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 30,
+                game_id: None,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info("backend", &[]), msg).unwrap();
+        }
+
+        let history = |start_after, limit| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerHistory {
+                    player: Addr::unchecked("alice"),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+            let value: GetPlayerHistoryResponse = from_json(&res).unwrap();
+            value
+                .games
+                .into_iter()
+                .map(|game| (game.record_id, game.score))
+                .collect::<Vec<_>>()
+        };
+
+        // Histórico do mais recente para o mais antigo, paginado
+        assert_eq!(vec![(4, 80), (3, 250), (1, 100)], history(None, None));
+        assert_eq!(vec![(4, 80), (3, 250)], history(None, Some(2)));
+        assert_eq!(vec![(1, 100)], history(Some(3), Some(2)));
+
+        // O score atual continua sendo o do último jogo
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetScoreByPlayer {
+                player: Addr::unchecked("alice"),
+                game_id: None,
+            },
+        )
+        .unwrap();
+        let value: GetScoreByPlayerResponse = from_json(&res).unwrap();
+        assert_eq!(80, value.score);
+
+        // Consulta global por id
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetGame { record_id: 2 },
+        )
+        .unwrap();
+        let value: GetGameResponse = from_json(&res).unwrap();
+        assert_eq!(Addr::unchecked("bob"), value.game.player);
+        assert_eq!(90, value.game.score);
+        assert_eq!(start.plus_seconds(60), value.game.recorded_at);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetGame { record_id: 5 },
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn player_stats_are_maintained_incrementally() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let start = env.block.time;
+        for (i, (player, score, game_time)) in [
+            ("alice", 100, 30),
+            ("bob", 300, 60),
+            ("alice", 250, 45),
+            ("alice", 80, 15),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            env.block.time = start.plus_seconds(i as u64 * 60);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time,
+                game_id: None,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info("backend", &[]), msg).unwrap();
+        }
+
+        let stats = |player: &str| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerStats {
+                    player: Addr::unchecked(player),
+                },
+            )
+            .unwrap();
+            let value: GetPlayerStatsResponse = from_json(&res).unwrap();
+            value
+        };
+
+        let alice = stats("alice");
+        assert_eq!(3, alice.games_played);
+        assert_eq!(250, alice.best_score);
+        assert_eq!(80, alice.last_score);
+        assert_eq!(143, alice.average_score);
+        assert_eq!(Uint128::new(90), alice.total_game_time);
+        assert_eq!(30, alice.average_game_time);
+        assert_eq!(start, alice.first_played);
+        assert_eq!(start.plus_seconds(180), alice.last_played);
+        // Bob tem o melhor score, então Alice fica em segundo
+        assert_eq!(2, alice.rank);
+
+        let bob = stats("bob");
+        assert_eq!(1, bob.games_played);
+        assert_eq!(1, bob.rank);
+
+        // Jogador sem jogos não tem estatísticas
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerStats {
+                player: Addr::unchecked("carol"),
+            },
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn player_rank_and_neighborhood() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        for (player, score) in [
+            ("alice", 500),
+            ("bob", 400),
+            ("carol", 400),
+            ("dave", 300),
+            ("erin", 200),
+            ("bob", 100),
+        ] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("backend", &[]), msg).unwrap();
+        }
+
+        // Empates dividem a posição: carol e bob ficam em 2º
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerRank {
+                player: Addr::unchecked("bob"),
+            },
+        )
+        .unwrap();
+        let value: GetPlayerRankResponse = from_json(&res).unwrap();
+        assert_eq!(2, value.position);
+        assert_eq!(400, value.score);
+        assert_eq!(5, value.total_players);
+
+        let around = |player: &str, above, below| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetRankAround {
+                    player: Addr::unchecked(player),
+                    above,
+                    below,
+                },
+            )
+            .unwrap();
+            let value: GetRankAroundResponse = from_json(&res).unwrap();
+            (
+                value.start_position,
+                value
+                    .rank
+                    .into_iter()
+                    .map(|(_, player)| player.to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            (2, vec!["carol".to_string(), "bob".into(), "dave".into()]),
+            around("bob", Some(1), Some(1))
+        );
+        // A janela é cortada no topo e no fim do ranking
+        assert_eq!(
+            (1, vec!["alice".to_string(), "carol".into()]),
+            around("alice", Some(2), Some(1))
+        );
+        assert_eq!(
+            (
+                1,
+                vec![
+                    "alice".to_string(),
+                    "carol".into(),
+                    "bob".into(),
+                    "dave".into(),
+                    "erin".into()
+                ]
+            ),
+            around("erin", None, None)
+        );
+
+        // Jogador fora do ranking
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerRank {
+                player: Addr::unchecked("frank"),
+            },
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn seasons_keep_their_own_leaderboard() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let start = env.block.time;
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let create_season = |name: &str, from: u64, to: u64| ExecuteMsg::CreateSeason {
+            name: name.to_string(),
+            start: start.plus_seconds(from),
+            end: start.plus_seconds(to),
+        };
+
+        // Somente o owner cria temporadas
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            create_season("Semana 1", 100, 1000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            create_season("Semana 1", 100, 1000),
+        )
+        .unwrap();
+
+        // Temporadas não podem se sobrepor nem terminar antes de começar
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            create_season("Semana 2", 900, 2000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SeasonOverlap { season_id: 1 }));
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            create_season("Semana 2", 2000, 1000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSeason {}));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            create_season("Semana 2", 1000, 2000),
+        )
+        .unwrap();
+
+        // Jogos fora de uma temporada só entram no ranking global
+        for (at, player, score) in [
+            (0, "carol", 500),
+            (200, "alice", 100),
+            (300, "bob", 300),
+            (1500, "alice", 50),
+        ] {
+            env.block.time = start.plus_seconds(at);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            let res = execute(deps.as_mut(), env.clone(), mock_info("backend", &[]), msg).unwrap();
+            let season_id = res
+                .attributes
+                .iter()
+                .find(|attr| attr.key == "season_id")
+                .map(|attr| attr.value.clone());
+            assert_eq!(at > 0, season_id.is_some());
+        }
+
+        let rank = |season_id, start_after| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetRank {
+                    game_id: None,
+                    season_id,
+                    start_after,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            let value: GetRankResponse = from_json(&res).unwrap();
+            value.rank
+        };
+
+        assert_eq!(
+            vec![
+                (300, Addr::unchecked("bob")),
+                (100, Addr::unchecked("alice"))
+            ],
+            rank(Some(1), None)
+        );
+        assert_eq!(
+            vec![(100, Addr::unchecked("alice"))],
+            rank(Some(1), Some((300, Addr::unchecked("bob"))))
+        );
+        assert_eq!(vec![(50, Addr::unchecked("alice"))], rank(Some(2), None));
+        assert_eq!(3, rank(None, None).len());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetRank {
+                game_id: None,
+                season_id: Some(3),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert!(res.is_err());
+
+        // A temporada ativa é encontrada pelo horário do bloco
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetSeason { season_id: None },
+        )
+        .unwrap();
+        let value: GetSeasonResponse = from_json(&res).unwrap();
+        assert_eq!(2, value.season.season_id);
+        assert_eq!(SeasonStatus::Active, value.season.status);
+        assert_eq!(1, value.season.total_games);
+
+        // Temporadas encerradas continuam consultáveis
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ListSeasons {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: ListSeasonsResponse = from_json(&res).unwrap();
+        assert_eq!(2, value.seasons.len());
+        assert_eq!("Semana 1", value.seasons[0].name);
+        assert_eq!(SeasonStatus::Finished, value.seasons[0].status);
+        assert_eq!(2, value.seasons[0].total_games);
+
+        env.block.time = start.plus_seconds(2000);
+        let res = query(deps.as_ref(), env, QueryMsg::GetSeason { season_id: None });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn emission_respects_caps_and_halving() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let start = env.block.time;
+        let emission = EmissionSchedule {
+            tokens_per_point: Decimal::percent(10),
+            player_daily_cap: Uint128::new(50),
+            daily_budget: Uint128::new(80),
+            halving_interval: 2 * 86_400,
+            start,
+        };
+
+        // A emissão precisa do token de recompensa
+        let mut msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            emission: Some(emission),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        let err = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RewardTokenNotSet {}));
+
+        msg.reward_token = Some("token".to_string());
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let mut play = |env: &Env, player: &str, score| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            let res = execute(deps.as_mut(), env.clone(), mock_info("backend", &[]), msg).unwrap();
+            res.attributes
+                .iter()
+                .find(|attr| attr.key == "earned")
+                .map(|attr| attr.value.parse::<u128>().unwrap())
+                .unwrap_or_default()
+        };
+
+        // Limite diário por jogador e orçamento global do dia
+        assert_eq!(30, play(&env, "alice", 300));
+        assert_eq!(20, play(&env, "alice", 300));
+        assert_eq!(30, play(&env, "bob", 300));
+        assert_eq!(0, play(&env, "carol", 100));
+
+        // Um novo dia renova os limites
+        env.block.time = start.plus_seconds(86_400);
+        assert_eq!(30, play(&env, "carol", 300));
+
+        // Depois do halving o orçamento diário cai pela metade
+        env.block.time = start.plus_seconds(2 * 86_400);
+        assert_eq!(30, play(&env, "alice", 300));
+        assert_eq!(10, play(&env, "bob", 300));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetEmissionStatus {
+                player: Some(Addr::unchecked("alice")),
+            },
+        )
+        .unwrap();
+        let value: GetEmissionStatusResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(40), value.daily_budget);
+        assert_eq!(Uint128::new(40), value.emitted_today);
+        assert_eq!(Uint128::zero(), value.remaining_today);
+        assert_eq!(Some(Uint128::new(20)), value.player_remaining_today);
+
+        // Os ganhos ficam disponíveis para resgate
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetPendingRewards {
+                player: Addr::unchecked("alice"),
+            },
+        )
+        .unwrap();
+        let value: GetPendingRewardsResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(80), value.token_rewards);
+    }
+
+    #[test]
+    fn registered_games_keep_their_own_leaderboard() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let register = |game_id: &str| ExecuteMsg::RegisterGame {
+            game_id: game_id.to_string(),
+            name: "Corrida".to_string(),
+            config: GameConfig {
+                rank_policy: RankPolicy::Cumulative,
+                game_rules: GameRules {
+                    max_score: Some(500),
+                    ..GameRules::default()
+                },
+            },
+        };
+
+        // Somente o owner registra jogos, e "default" é reservado
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            register("race"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        for game_id in ["default", "", "com espaço"] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                register(game_id),
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::InvalidGameId {}));
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            register("race"),
+        )
+        .unwrap();
+
+        let new_game = |score: u64, game_id: Option<&str>| ExecuteMsg::NewGame {
+            player: Addr::unchecked("alice"),
+            score,
+            game_time: 60,
+            game_id: game_id.map(str::to_string),
+        };
+
+        // Cada jogo tem seus próprios submitters
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            new_game(100, Some("race")),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            new_game(100, Some("puzzle")),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::UnknownGame { .. }));
+
+        let msg = ExecuteMsg::AddSubmitter {
+            address: "race-backend".to_string(),
+            game_id: Some("race".to_string()),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        // As regras e a política de ranking são as do jogo
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("race-backend", &[]),
+            new_game(600, Some("race")),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ScoreTooHigh { max: 500 }));
+        for score in [100, 50] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("race-backend", &[]),
+                new_game(score, Some("race")),
+            )
+            .unwrap();
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("backend", &[]),
+            new_game(700, Some("default")),
+        )
+        .unwrap();
+
+        let rank = |game_id: Option<&str>| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetRank {
+                    game_id: game_id.map(str::to_string),
+                    season_id: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            from_json::<GetRankResponse>(&res).unwrap().rank
+        };
+        assert_eq!(vec![(150, Addr::unchecked("alice"))], rank(Some("race")));
+        assert_eq!(vec![(700, Addr::unchecked("alice"))], rank(None));
+
+        let total = |game_id: Option<&str>| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetTotal {
+                    game_id: game_id.map(str::to_string),
+                },
+            )
+            .unwrap();
+            from_json::<GetTotalResponse>(&res).unwrap().total
+        };
+        assert_eq!(2, total(Some("race")));
+        assert_eq!(1, total(None));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetScoreByPlayer {
+                player: Addr::unchecked("alice"),
+                game_id: Some("race".to_string()),
+            },
+        )
+        .unwrap();
+        let value: GetScoreByPlayerResponse = from_json(&res).unwrap();
+        assert_eq!(50, value.score);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListGames {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: ListGamesResponse = from_json(&res).unwrap();
+        assert_eq!(1, value.games.len());
+        assert_eq!("race", value.games[0].game_id);
+        assert_eq!(2, value.games[0].total);
+    }
+
+    #[test]
+    fn clubs_rank_by_member_scores() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let mut run = |sender: &str, msg: ExecuteMsg| {
+            execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg)
+        };
+        let new_game = |player: &str, score: u64| ExecuteMsg::NewGame {
+            player: Addr::unchecked(player),
+            score,
+            game_time: 60,
+            game_id: None,
+        };
+
+        // O melhor score de quem entra já conta para o clube
+        run("backend", new_game("alice", 100)).unwrap();
+        run(
+            "alice",
+            ExecuteMsg::CreateClub {
+                name: "Tapadores".to_string(),
+                open: true,
+                max_members: 3,
+                scoring: ClubScoring::Sum,
+            },
+        )
+        .unwrap();
+        let err = run(
+            "bob",
+            ExecuteMsg::CreateClub {
+                name: "Elite".to_string(),
+                open: false,
+                max_members: 2,
+                scoring: ClubScoring::TopK { k: 3 },
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidClub {}));
+        run(
+            "bob",
+            ExecuteMsg::CreateClub {
+                name: "Elite".to_string(),
+                open: false,
+                max_members: 2,
+                scoring: ClubScoring::TopK { k: 1 },
+            },
+        )
+        .unwrap();
+
+        // Clubes fechados exigem convite do dono
+        let err = run("carol", ExecuteMsg::JoinClub { id: 2 }).unwrap_err();
+        assert!(matches!(err, ContractError::ClubInviteOnly { club_id: 2 }));
+        let invite = |player: &str| ExecuteMsg::InviteToClub {
+            id: 2,
+            player: player.to_string(),
+        };
+        let err = run("alice", invite("carol")).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        run("bob", invite("carol")).unwrap();
+        run("carol", ExecuteMsg::JoinClub { id: 2 }).unwrap();
+        run("bob", invite("eve")).unwrap();
+        let err = run("eve", ExecuteMsg::JoinClub { id: 2 }).unwrap_err();
+        assert!(matches!(err, ContractError::ClubFull { club_id: 2 }));
+
+        run("dave", ExecuteMsg::JoinClub { id: 1 }).unwrap();
+        let err = run("dave", ExecuteMsg::JoinClub { id: 1 }).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyInClub { club_id: 1 }));
+
+        for (player, score) in [("bob", 300), ("carol", 200), ("dave", 50), ("alice", 120)] {
+            run("backend", new_game(player, score)).unwrap();
+        }
+        // Um score pior não muda o clube
+        run("backend", new_game("alice", 10)).unwrap();
+
+        let club_rank = |deps: &OwnedDeps<_, _, _>| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetClubRank {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            from_json::<GetClubRankResponse>(&res).unwrap().rank
+        };
+        assert_eq!(vec![(300, 2), (170, 1)], club_rank(&deps));
+
+        let get_club = |deps: &OwnedDeps<_, _, _>, start_after| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetClub {
+                    id: 1,
+                    start_after,
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+            from_json::<GetClubResponse>(&res).unwrap()
+        };
+        let club = get_club(&deps, None);
+        assert_eq!(2, club.club.members);
+        assert_eq!(vec![(120, Addr::unchecked("alice"))], club.members);
+        let club = get_club(&deps, Some((120, Addr::unchecked("alice"))));
+        assert_eq!(vec![(50, Addr::unchecked("dave"))], club.members);
+
+        // O dono só sai por último, e o clube some com ele
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::LeaveClub {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ClubOwnerCannotLeave {}));
+        for player in ["dave", "alice"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(player, &[]),
+                ExecuteMsg::LeaveClub {},
+            )
+            .unwrap();
+        }
+        assert_eq!(vec![(300, 2)], club_rank(&deps));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetPlayerClub {
+                player: Addr::unchecked("carol"),
+            },
+        )
+        .unwrap();
+        let value: GetPlayerClubResponse = from_json(&res).unwrap();
+        assert_eq!(Some(2), value.club_id);
+    }
+
+    #[test]
+    fn streaks_multiply_earnings() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let start = env.block.time;
+
+        let mut msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            reward_token: Some("token".to_string()),
+            emission: Some(EmissionSchedule {
+                tokens_per_point: Decimal::one(),
+                player_daily_cap: Uint128::new(1000),
+                daily_budget: Uint128::new(1000),
+                halving_interval: 365 * 86_400,
+                start,
+            }),
+            streak: Some(StreakConfig {
+                multipliers: vec![(2, Decimal::percent(150)), (3, Decimal::percent(200))],
+                freeze_price: Some(coin(10, "denom")),
+            }),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+
+        // Vender congelamentos exige a tesouraria
+        let err = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStreakConfig {}));
+        msg.treasury = Some("treasury".to_string());
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        // Os dias da tabela de multiplicadores precisam ser crescentes
+        let msg = ExecuteMsg::SetStreakConfig {
+            streak: StreakConfig {
+                multipliers: vec![(3, Decimal::percent(200)), (2, Decimal::percent(150))],
+                freeze_price: None,
+            },
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStreakConfig {}));
+
+        let play = |deps: DepsMut, env: &Env, score| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked("alice"),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps, env.clone(), mock_info("backend", &[]), msg).unwrap()
+        };
+        let attribute = |res: &Response, key: &str| {
+            res.attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.parse::<u128>().unwrap())
+                .unwrap_or_default()
+        };
+
+        // Vários jogos no mesmo dia contam um dia só
+        assert_eq!(10, attribute(&play(deps.as_mut(), &env, 10), "earned"));
+        assert_eq!(10, attribute(&play(deps.as_mut(), &env, 10), "earned"));
+        env.block.time = start.plus_seconds(86_400);
+        assert_eq!(15, attribute(&play(deps.as_mut(), &env, 10), "earned"));
+
+        // O congelamento é pago à tesouraria e só serve a quem tem sequência
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &coins(10, "denom")),
+            ExecuteMsg::BuyStreakFreeze {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoActiveStreak {}));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(5, "denom")),
+            ExecuteMsg::BuyStreakFreeze {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::WrongStreakFreezePrice { .. }));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(10, "denom")),
+            ExecuteMsg::BuyStreakFreeze {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: coins(10, "denom"),
+            })
+        );
+
+        // O congelamento cobre um dia sem jogos
+        env.block.time = start.plus_seconds(3 * 86_400);
+        assert_eq!(20, attribute(&play(deps.as_mut(), &env, 10), "earned"));
+
+        let streak = |env: &Env| {
+            let res = query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetStreak {
+                    player: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap();
+            from_json::<GetStreakResponse>(&res).unwrap()
+        };
+        let value = streak(&env);
+        assert_eq!((3, 3, 0), (value.current, value.longest, value.freezes));
+        assert_eq!(Decimal::percent(200), value.multiplier);
+
+        // Sem congelamentos a sequência se quebra
+        env.block.time = start.plus_seconds(5 * 86_400);
+        let value = streak(&env);
+        assert_eq!((0, 3), (value.current, value.longest));
+        assert_eq!(Decimal::one(), value.multiplier);
+        let res = play(deps.as_mut(), &env, 10);
+        assert_eq!(1, attribute(&res, "streak"));
+    }
+
+    #[test]
+    fn referrers_earn_a_share() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            reward_token: Some("token".to_string()),
+            emission: Some(EmissionSchedule {
+                tokens_per_point: Decimal::one(),
+                player_daily_cap: Uint128::new(1000),
+                daily_budget: Uint128::new(1000),
+                halving_interval: 365 * 86_400,
+                start: env.block.time,
+            }),
+            referral_share: Some(Decimal::percent(10)),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let refer = |deps: DepsMut, player: &str, referrer: &str| {
+            let msg = ExecuteMsg::RegisterReferrer {
+                referrer: referrer.to_string(),
+            };
+            execute(deps, mock_env(), mock_info(player, &[]), msg)
+        };
+        let play = |deps: DepsMut, player: &str, score| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps, mock_env(), mock_info("backend", &[]), msg).unwrap();
+        };
+
+        // Ninguém indica a si mesmo nem fecha um ciclo
+        let err = refer(deps.as_mut(), "alice", "alice").unwrap_err();
+        assert!(matches!(err, ContractError::InvalidReferrer {}));
+        refer(deps.as_mut(), "bob", "alice").unwrap();
+        refer(deps.as_mut(), "carol", "bob").unwrap();
+        let err = refer(deps.as_mut(), "alice", "carol").unwrap_err();
+        assert!(matches!(err, ContractError::InvalidReferrer {}));
+
+        // A indicação é única e vem antes da primeira partida
+        let err = refer(deps.as_mut(), "bob", "carol").unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyReferred {}));
+        play(deps.as_mut(), "dave", 10);
+        let err = refer(deps.as_mut(), "dave", "alice").unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyPlayed {}));
+
+        // O indicador recebe 10% dos ganhos, além do que o indicado ganha
+        play(deps.as_mut(), "bob", 200);
+        play(deps.as_mut(), "carol", 100);
+        let pending = |player: &str| {
+            let res = query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetPendingRewards {
+                    player: Addr::unchecked(player),
+                },
+            )
+            .unwrap();
+            from_json::<GetPendingRewardsResponse>(&res)
+                .unwrap()
+                .token_rewards
+        };
+        // Bob ganha seus 200 e mais 10 por ter indicado a Carol
+        assert_eq!(Uint128::new(210), pending("bob"));
+        assert_eq!(Uint128::new(20), pending("alice"));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetReferral {
+                player: Addr::unchecked("bob"),
+            },
+        )
+        .unwrap();
+        let value: GetReferralResponse = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("alice")), value.referrer);
+        assert_eq!(Uint128::new(10), value.earnings);
+
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetReferees {
+                referrer: Addr::unchecked("alice"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: GetRefereesResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![(Addr::unchecked("bob"), Uint128::new(20))],
+            value.referees
+        );
+    }
+
+    #[test]
+    fn pause_and_circuit_breaker() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            max_games_per_block: Some(2),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let play = |deps: DepsMut, env: &Env| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked("alice"),
+                score: 100,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps, env.clone(), mock_info("backend", &[]), msg)
+        };
+        let pause = |deps: Deps| {
+            let res = query(deps, mock_env(), QueryMsg::GetPause {}).unwrap();
+            from_json::<GetPauseResponse>(&res).unwrap()
+        };
+        let total = |deps: Deps| {
+            let res = query(deps, mock_env(), QueryMsg::GetTotal { game_id: None }).unwrap();
+            from_json::<GetTotalResponse>(&res).unwrap().total
+        };
+
+        // Passar do teto de partidas do bloco pausa o contrato sem registrar a partida
+        play(deps.as_mut(), &env).unwrap();
+        play(deps.as_mut(), &env).unwrap();
+        let res = play(deps.as_mut(), &env).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "reason" && attr.value == "submission_ceiling"));
+        assert_eq!(2, total(deps.as_ref()));
+        let value = pause(deps.as_ref());
+        assert!(value.paused);
+        assert_eq!(Some(PauseReason::SubmissionCeiling), value.reason);
+
+        // Pausado, partidas e resgates são recusados, mas as consultas funcionam
+        env.block.height += 1;
+        let err = play(deps.as_mut(), &env).unwrap_err();
+        assert!(matches!(err, ContractError::Paused {}));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            ExecuteMsg::ClaimRewards {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused {}));
+        assert_eq!(2, total(deps.as_ref()));
+
+        // Nenhum fundo entra no contrato ou segue para a tesouraria
+        for msg in [
+            ExecuteMsg::BuyStreakFreeze {},
+            ExecuteMsg::DepositToClub { id: 1 },
+            ExecuteMsg::JoinTournament { id: 1 },
+            ExecuteMsg::FundSeason { season_id: 1 },
+            ExecuteMsg::FundSeasonClubPrize { season_id: 1 },
+        ] {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("alice", &coins(100, "uxion")),
+                msg,
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Paused {}));
+        }
+
+        // Somente o owner pausa e despausa
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap();
+        assert!(!pause(deps.as_ref()).paused);
+        play(deps.as_mut(), &env).unwrap();
+        assert_eq!(3, total(deps.as_ref()));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap();
+        assert_eq!(Some(PauseReason::Admin), pause(deps.as_ref()).reason);
+        let err = play(deps.as_mut(), &env).unwrap_err();
+        assert!(matches!(err, ContractError::Paused {}));
+    }
+
+    #[test]
+    fn roles_guard_privileged_messages() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            owner: Some("admin".to_string()),
+            submitters: Some(vec!["backend".to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let run = |deps: DepsMut, sender: &str, msg: ExecuteMsg| {
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        };
+        let grant = |role, address: &str| ExecuteMsg::GrantRole {
+            role,
+            address: address.to_string(),
+        };
+        let set_game_rules = || ExecuteMsg::SetGameRules {
+            game_rules: GameRules::default(),
+        };
+
+        // Somente o owner distribui papéis, e o de owner muda com TransferOwnership
+        let err = run(deps.as_mut(), "ops", grant(Role::Operator, "ops")).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let err = run(deps.as_mut(), "admin", grant(Role::Owner, "ops")).unwrap_err();
+        assert!(matches!(err, ContractError::OwnerRole {}));
+        run(deps.as_mut(), "admin", grant(Role::Operator, "ops")).unwrap();
+        run(deps.as_mut(), "admin", grant(Role::Pauser, "guard")).unwrap();
+
+        // Cada papel libera somente as suas mensagens
+        run(deps.as_mut(), "ops", set_game_rules()).unwrap();
+        let err = run(deps.as_mut(), "ops", ExecuteMsg::Pause {}).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let err = run(deps.as_mut(), "guard", set_game_rules()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        run(deps.as_mut(), "guard", ExecuteMsg::Pause {}).unwrap();
+        run(deps.as_mut(), "guard", ExecuteMsg::Unpause {}).unwrap();
+
+        let has_role = |deps: Deps, role, address: &str| {
+            let msg = QueryMsg::HasRole {
+                role,
+                address: Addr::unchecked(address),
+            };
+            let res = query(deps, mock_env(), msg).unwrap();
+            from_json::<HasRoleResponse>(&res).unwrap().has_role
+        };
+        assert!(has_role(deps.as_ref(), Role::Operator, "ops"));
+        assert!(!has_role(deps.as_ref(), Role::Treasurer, "ops"));
+        // O owner tem todos os papéis, menos o de registrar partidas
+        assert!(has_role(deps.as_ref(), Role::Treasurer, "admin"));
+        assert!(!has_role(deps.as_ref(), Role::ScoreSubmitter, "admin"));
+        assert!(has_role(deps.as_ref(), Role::ScoreSubmitter, "backend"));
+
+        let members = |deps: Deps, role| {
+            let msg = QueryMsg::ListRoleMembers {
+                role,
+                start_after: None,
+                limit: None,
+            };
+            let res = query(deps, mock_env(), msg).unwrap();
+            from_json::<ListRoleMembersResponse>(&res).unwrap().members
+        };
+        assert_eq!(
+            vec![Addr::unchecked("ops")],
+            members(deps.as_ref(), Role::Operator)
+        );
+        assert_eq!(
+            vec![Addr::unchecked("admin")],
+            members(deps.as_ref(), Role::Owner)
+        );
+        assert_eq!(
+            vec![Addr::unchecked("backend")],
+            members(deps.as_ref(), Role::ScoreSubmitter)
+        );
+
+        // Papel revogado perde o acesso
+        let msg = ExecuteMsg::RevokeRole {
+            role: Role::Operator,
+            address: "ops".to_string(),
+        };
+        run(deps.as_mut(), "admin", msg).unwrap();
+        let err = run(deps.as_mut(), "ops", set_game_rules()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        assert!(members(deps.as_ref(), Role::Operator).is_empty());
+    }
+
+    #[test]
+    fn rank_position_follows_score_changes() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            submitters: Some(vec!["backend".to_string()]),
+            rank_policy: Some(RankPolicy::Latest),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let play = |deps: DepsMut, player: &str, score| {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            execute(deps, mock_env(), mock_info("backend", &[]), msg).unwrap();
+        };
+        let position = |deps: Deps, player: &str| {
+            let msg = QueryMsg::GetPlayerRank {
+                player: Addr::unchecked(player),
+            };
+            let res = query(deps, mock_env(), msg).unwrap();
+            from_json::<GetPlayerRankResponse>(&res).unwrap().position
+        };
+
+        // Scores em ordens de grandeza diferentes
+        for (player, score) in [
+            ("alice", u64::MAX),
+            ("bob", 1 << 40),
+            ("carol", 4096),
+            ("dave", 255),
+            ("erin", 16),
+            ("frank", 15),
+            ("grace", 0),
+        ] {
+            play(deps.as_mut(), player, score);
+        }
+        for (expected, player) in ["alice", "bob", "carol", "dave", "erin", "frank", "grace"]
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(expected as u64 + 1, position(deps.as_ref(), player));
+        }
+
+        // Com a política Latest o score também cai
+        play(deps.as_mut(), "alice", 17);
+        play(deps.as_mut(), "grace", 4097);
+        assert_eq!(5, position(deps.as_ref(), "alice"));
+        assert_eq!(2, position(deps.as_ref(), "grace"));
+        assert_eq!(1, position(deps.as_ref(), "bob"));
+        assert_eq!(6, position(deps.as_ref(), "erin"));
+        assert_eq!(7, position(deps.as_ref(), "frank"));
+    }
 }
@@ -1,18 +1,60 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{
+        coins, to_json_binary, Addr, Coin, Decimal, Empty, Order, Record, Storage, Uint128,
+    };
+    use cw20::{
+        BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse,
+    };
+    use cw721::{NftInfoResponse, TokensResponse};
+    use cw721_base::Cw721Contract;
     use cw_counter::helpers::CwCounterContract;
-    use cw_counter::msg::execute::ExecuteMsg;
+    use cw_counter::msg::execute::{AchievementMetadata, ExecuteMsg, ReceiveMsg};
     use cw_counter::msg::instantiate::InstantiateMsg;
+    use cw_counter::msg::migrate::MigrateMsg;
     use cw_counter::msg::query::QueryMsg;
-    use cw_counter::msg::response::{GetRankResponse, GetScoreByPlayerResponse, GetTotalResponse};
-    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_counter::msg::response::{
+        GetClubPayoutsResponse, GetClubRankResponse, GetClubTreasuryResponse,
+        GetPendingRewardsResponse, GetPlayerAchievementsResponse, GetRankResponse,
+        GetScoreByPlayerResponse, GetSeasonPayoutsResponse, GetSeasonResponse, GetTotalResponse,
+        GetTournamentResponse, ListAchievementsResponse,
+    };
+    use cw_counter::state::model::{
+        AchievementRule, ClubScoring, ClubSplit, EmissionSchedule, Role, SessionLimits,
+        TournamentState,
+    };
+    use cw_multi_test::{App, AppBuilder, BankKeeper, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
             cw_counter::contract::execute,
             cw_counter::contract::instantiate,
             cw_counter::contract::query,
+        )
+        .with_migrate(cw_counter::contract::migrate)
+        .with_reply(cw_counter::contract::reply);
+        Box::new(contract)
+    }
+
+    pub fn cw20_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn cw721_template() -> Box<dyn Contract<Empty>> {
+        type Cw721<'a> = Cw721Contract<'a, AchievementMetadata, Empty, Empty, Empty>;
+        let contract = ContractWrapper::new(
+            |deps, env, info, msg| Cw721::default().execute(deps, env, info, msg),
+            |deps, env, info, msg| Cw721::default().instantiate(deps, env, info, msg),
+            |deps, env, msg| Cw721::default().query(deps, env, msg),
         );
         Box::new(contract)
     }
@@ -21,7 +63,17 @@ mod tests {
     const USER2: &str = "bob";
     const USER3: &str = "charlie";
     const ADMIN: &str = "admin";
+    const SUBMITTER: &str = "backend";
     const NATIVE_DENOM: &str = "denom";
+    const TREASURY: &str = "treasury";
+
+    // Lets NewGame record default game plays, sessions are required otherwise
+    fn trusted_game_time() -> Option<SessionLimits> {
+        Some(SessionLimits {
+            sessions_only: false,
+            ..SessionLimits::default()
+        })
+    }
 
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
@@ -45,7 +97,11 @@ mod tests {
         let mut app = mock_app();
         let contract_id = app.store_code(contract_template());
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            submitters: Some(vec![SUBMITTER.to_string()]),
+            session_limits: trusted_game_time(),
+            ..Default::default()
+        };
         let contract_addr = app
             .instantiate_contract(
                 contract_id,
@@ -53,7 +109,7 @@ mod tests {
                 &msg,
                 &[],
                 "cw-counter",
-                None,
+                Some(ADMIN.to_string()),
             )
             .unwrap();
 
@@ -70,32 +126,35 @@ mod tests {
             player: Addr::unchecked(USER1),
             score: 150,
             game_time: 60,
+            game_id: None,
         };
         let cosmos_msg = contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER1), cosmos_msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
 
         // Bob joga
         let msg = ExecuteMsg::NewGame {
             player: Addr::unchecked(USER2),
             score: 200,
             game_time: 45,
+            game_id: None,
         };
         let cosmos_msg = contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER2), cosmos_msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
 
         // Charlie joga
         let msg = ExecuteMsg::NewGame {
             player: Addr::unchecked(USER3),
             score: 100,
             game_time: 75,
+            game_id: None,
         };
         let cosmos_msg = contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER3), cosmos_msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
 
         // Verificar total de jogos
         let total: GetTotalResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal {})
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal { game_id: None })
             .unwrap();
         assert_eq!(3, total.total);
 
@@ -106,6 +165,7 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER1),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -117,6 +177,7 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER2),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -125,7 +186,15 @@ mod tests {
         // Verificar ranking
         let rank: GetRankResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetRank {})
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetRank {
+                    game_id: None,
+                    season_id: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
             .unwrap();
 
         assert_eq!(3, rank.rank.len());
@@ -144,18 +213,20 @@ mod tests {
             player: Addr::unchecked(USER1),
             score: 100,
             game_time: 60,
+            game_id: None,
         };
         let cosmos_msg = contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER1), cosmos_msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
 
         // Alice melhora seu score
         let msg = ExecuteMsg::NewGame {
             player: Addr::unchecked(USER1),
             score: 250,
             game_time: 45,
+            game_id: None,
         };
         let cosmos_msg = contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER1), cosmos_msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
 
         // Verificar que o score atual é o melhor
         let alice_score: GetScoreByPlayerResponse = app
@@ -164,25 +235,33 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER1),
+                    game_id: None,
                 },
             )
             .unwrap();
         assert_eq!(250, alice_score.score);
 
-        // Verificar que ambos os jogos estão no ranking
+        // Verificar que o jogador ocupa uma única posição no ranking
         let rank: GetRankResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetRank {})
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetRank {
+                    game_id: None,
+                    season_id: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
             .unwrap();
 
-        assert_eq!(2, rank.rank.len());
+        assert_eq!(1, rank.rank.len());
         assert_eq!((250, Addr::unchecked(USER1)), rank.rank[0]);
-        assert_eq!((100, Addr::unchecked(USER1)), rank.rank[1]);
 
         // Verificar total de jogos
         let total: GetTotalResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal {})
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal { game_id: None })
             .unwrap();
         assert_eq!(2, total.total);
     }
@@ -206,21 +285,33 @@ mod tests {
                 player: Addr::unchecked(player),
                 score,
                 game_time,
+                game_id: None,
             };
             let cosmos_msg = contract.call(msg).unwrap();
-            app.execute(Addr::unchecked(player), cosmos_msg).unwrap();
+            app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
         }
 
         // Verificar ranking final
         let rank: GetRankResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetRank {})
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetRank {
+                    game_id: None,
+                    season_id: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
             .unwrap();
 
-        assert_eq!(6, rank.rank.len());
+        assert_eq!(3, rank.rank.len());
 
         // Verificar que Charlie tem o melhor score (280)
         assert_eq!((280, Addr::unchecked(USER3)), rank.rank[0]);
+        assert_eq!((240, Addr::unchecked(USER1)), rank.rank[1]);
+        // Bob mantém o melhor score (220) mesmo tendo piorado
+        assert_eq!((220, Addr::unchecked(USER2)), rank.rank[2]);
 
         // Verificar scores atuais dos jogadores (último jogo de cada um)
         let charlie_score: GetScoreByPlayerResponse = app
@@ -229,6 +320,7 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER3),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -240,6 +332,7 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER1),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -251,6 +344,7 @@ mod tests {
                 contract.addr(),
                 &QueryMsg::GetScoreByPlayer {
                     player: Addr::unchecked(USER2),
+                    game_id: None,
                 },
             )
             .unwrap();
@@ -259,8 +353,1159 @@ mod tests {
         // Verificar total de jogos
         let total: GetTotalResponse = app
             .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal {})
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetTotal { game_id: None })
             .unwrap();
         assert_eq!(6, total.total);
     }
+
+    #[test]
+    fn test_migrate_keeps_scores() {
+        let (mut app, contract) = proper_instantiate();
+
+        let msg = ExecuteMsg::NewGame {
+            player: Addr::unchecked(USER1),
+            score: 150,
+            game_time: 60,
+            game_id: None,
+        };
+        let cosmos_msg = contract.call(msg).unwrap();
+        app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
+
+        // Somente o admin do contrato pode migrar
+        let code_id = app.store_code(contract_template());
+        app.migrate_contract(
+            Addr::unchecked(USER1),
+            contract.addr(),
+            &MigrateMsg {},
+            code_id,
+        )
+        .unwrap_err();
+        app.migrate_contract(
+            Addr::unchecked(ADMIN),
+            contract.addr(),
+            &MigrateMsg {},
+            code_id,
+        )
+        .unwrap();
+
+        // Os scores continuam disponíveis após a migração
+        let rank: GetRankResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetRank {
+                    game_id: None,
+                    season_id: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(vec![(150, Addr::unchecked(USER1))], rank.rank);
+    }
+
+    // Custos de gas do KVStore do Cosmos SDK
+    const READ_COST_FLAT: u64 = 1000;
+    const READ_COST_PER_BYTE: u64 = 3;
+    const WRITE_COST_FLAT: u64 = 2000;
+    const WRITE_COST_PER_BYTE: u64 = 30;
+    const DELETE_COST: u64 = 1000;
+    const ITER_NEXT_COST_FLAT: u64 = 30;
+
+    /// Storage that charges gas the same way the chain KVStore does, so the
+    /// cost of each transaction can be compared in multi-test.
+    struct GasMeteredStorage {
+        inner: MockStorage,
+        gas: Rc<Cell<u64>>,
+    }
+
+    impl GasMeteredStorage {
+        fn charge(&self, amount: u64) {
+            self.gas.set(self.gas.get() + amount);
+        }
+    }
+
+    impl Storage for GasMeteredStorage {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            let value = self.inner.get(key);
+            let bytes = key.len() + value.as_ref().map_or(0, Vec::len);
+            self.charge(READ_COST_FLAT + READ_COST_PER_BYTE * bytes as u64);
+            value
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = Record> + 'a> {
+            let gas = self.gas.clone();
+            Box::new(self.inner.range(start, end, order).inspect(move |(k, v)| {
+                let bytes = (k.len() + v.len()) as u64;
+                gas.set(gas.get() + ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * bytes);
+            }))
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.charge(WRITE_COST_FLAT + WRITE_COST_PER_BYTE * (key.len() + value.len()) as u64);
+            self.inner.set(key, value);
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.charge(DELETE_COST);
+            self.inner.remove(key);
+        }
+    }
+
+    #[test]
+    fn test_season_prizes_are_claimed() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        let msg = ExecuteMsg::SetPayoutTable {
+            payout_table: vec![Decimal::percent(50), Decimal::percent(30)],
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        let msg = ExecuteMsg::CreateSeason {
+            name: "Semana 1".to_string(),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+
+        // Qualquer um pode financiar o prêmio da temporada
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            contract.addr(),
+            &ExecuteMsg::FundSeason { season_id: 1 },
+            &coins(1000, NATIVE_DENOM),
+        )
+        .unwrap();
+
+        app.update_block(|block| block.time = now.plus_seconds(100));
+        for (player, score) in [(USER1, 150), (USER2, 200), (USER3, 100)] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            app.execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        // A temporada só é finalizada depois de terminar
+        let msg = ExecuteMsg::FinalizeSeason { season_id: 1 };
+        app.execute(Addr::unchecked(USER3), contract.call(msg.clone()).unwrap())
+            .unwrap_err();
+
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        app.execute(Addr::unchecked(USER3), contract.call(msg.clone()).unwrap())
+            .unwrap();
+
+        let balance = |app: &App, address: &str| {
+            app.wrap()
+                .query_balance(address, NATIVE_DENOM)
+                .unwrap()
+                .amount
+                .u128()
+        };
+        let pending = |app: &App, player: &str| {
+            let value: GetPendingRewardsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::GetPendingRewards {
+                        player: Addr::unchecked(player),
+                    },
+                )
+                .unwrap();
+            value.rewards
+        };
+
+        // Os prêmios são creditados e cada jogador saca o seu
+        assert_eq!(1000, balance(&app, USER2));
+        assert_eq!(coins(500, NATIVE_DENOM), pending(&app, USER2));
+        assert_eq!(coins(300, NATIVE_DENOM), pending(&app, USER1));
+        assert!(pending(&app, USER3).is_empty());
+
+        let claim = ExecuteMsg::ClaimRewards {};
+        app.execute(
+            Addr::unchecked(USER2),
+            contract.call(claim.clone()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(1500, balance(&app, USER2));
+        assert!(pending(&app, USER2).is_empty());
+        app.execute(Addr::unchecked(USER2), contract.call(claim).unwrap())
+            .unwrap_err();
+
+        let payouts: GetSeasonPayoutsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetSeasonPayouts { season_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(
+            vec![(1, Addr::unchecked(USER2)), (2, Addr::unchecked(USER1))],
+            payouts
+                .payouts
+                .into_iter()
+                .map(|payout| (payout.position, payout.player))
+                .collect::<Vec<_>>()
+        );
+
+        // O que não foi distribuído continua registrado no prêmio
+        let season: GetSeasonResponse = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetSeason { season_id: Some(1) })
+            .unwrap();
+        assert!(season.season.finalized);
+        assert_eq!(coins(200, NATIVE_DENOM), season.season.prize_pool);
+
+        // Não é possível pagar duas vezes
+        app.execute(Addr::unchecked(USER3), contract.call(msg).unwrap())
+            .unwrap_err();
+        assert!(pending(&app, USER2).is_empty());
+
+        // Prêmios não sacados no prazo voltam para o owner, mesmo quando
+        // quem recolhe é um tesoureiro
+        app.execute(
+            Addr::unchecked(ADMIN),
+            contract
+                .call(ExecuteMsg::GrantRole {
+                    role: Role::Treasurer,
+                    address: USER3.to_string(),
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        let treasurer_balance = balance(&app, USER3);
+        let sweep = ExecuteMsg::SweepRewards {
+            players: vec![USER1.to_string()],
+        };
+        app.execute(
+            Addr::unchecked(ADMIN),
+            contract.call(sweep.clone()).unwrap(),
+        )
+        .unwrap_err();
+
+        app.update_block(|block| block.time = now.plus_seconds(1000 + 30 * 24 * 60 * 60));
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(sweep.clone()).unwrap(),
+        )
+        .unwrap_err();
+        app.execute(Addr::unchecked(USER3), contract.call(sweep).unwrap())
+            .unwrap();
+        assert_eq!(300, balance(&app, ADMIN));
+        assert_eq!(treasurer_balance, balance(&app, USER3));
+        assert_eq!(1000, balance(&app, USER1));
+        assert!(pending(&app, USER1).is_empty());
+        assert_eq!(200, balance(&app, contract.addr().as_str()));
+    }
+
+    #[test]
+    fn test_tournament_entry_fees_pay_the_winners() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        let msg = ExecuteMsg::SetHouseFee {
+            house_fee: Decimal::percent(10),
+            treasury: Some(TREASURY.to_string()),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        let msg = ExecuteMsg::CreateTournament {
+            entry_fee: Coin::new(100, NATIVE_DENOM),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+            max_players: 3,
+            min_players: None,
+            payout_table: vec![Decimal::percent(70), Decimal::percent(30)],
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        let msg = ExecuteMsg::CreateTournament {
+            entry_fee: Coin::new(100, NATIVE_DENOM),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+            max_players: 5,
+            min_players: Some(3),
+            payout_table: vec![Decimal::percent(100)],
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+
+        let join = |app: &mut App, player: &str, id: u64, amount: u128| {
+            app.execute_contract(
+                Addr::unchecked(player),
+                contract.addr(),
+                &ExecuteMsg::JoinTournament { id },
+                &coins(amount, NATIVE_DENOM),
+            )
+        };
+        let balance = |app: &App, address: &str| {
+            app.wrap()
+                .query_balance(address, NATIVE_DENOM)
+                .unwrap()
+                .amount
+                .u128()
+        };
+
+        // A inscrição exige exatamente a taxa de entrada
+        join(&mut app, USER1, 1, 50).unwrap_err();
+        join(&mut app, USER1, 1, 100).unwrap();
+        join(&mut app, USER1, 1, 100).unwrap_err();
+        join(&mut app, USER2, 1, 100).unwrap();
+        join(&mut app, USER3, 1, 100).unwrap();
+        join(&mut app, ADMIN, 1, 100).unwrap_err();
+        join(&mut app, ADMIN, 2, 100).unwrap();
+        assert_eq!(900, balance(&app, USER1));
+
+        // Depois do início não há mais inscrições
+        app.update_block(|block| block.time = now.plus_seconds(100));
+        join(&mut app, USER1, 2, 100).unwrap_err();
+
+        for (player, score) in [(USER1, 150), (USER2, 200), (ADMIN, 300)] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            app.execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        // Só os inscritos pontuam no torneio
+        let rank: GetRankResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetTournamentRank {
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            vec![(200, Addr::unchecked(USER2)), (150, Addr::unchecked(USER1))],
+            rank.rank
+        );
+
+        // Sem jogadores suficientes a inscrição é devolvida
+        let refund = ExecuteMsg::RefundTournament { id: 2 };
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(refund.clone()).unwrap(),
+        )
+        .unwrap_err();
+        app.execute(
+            Addr::unchecked(ADMIN),
+            contract.call(refund.clone()).unwrap(),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(ADMIN), contract.call(refund).unwrap())
+            .unwrap_err();
+        assert_eq!(1000, balance(&app, ADMIN));
+
+        // O torneio só é pago depois de terminar
+        let settle = ExecuteMsg::SettleTournament { id: 1 };
+        app.execute(
+            Addr::unchecked(USER3),
+            contract.call(settle.clone()).unwrap(),
+        )
+        .unwrap_err();
+
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        app.execute(
+            Addr::unchecked(USER3),
+            contract.call(settle.clone()).unwrap(),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(USER3), contract.call(settle).unwrap())
+            .unwrap_err();
+        let msg = ExecuteMsg::RefundTournament { id: 1 };
+        app.execute(Addr::unchecked(USER3), contract.call(msg).unwrap())
+            .unwrap_err();
+
+        // A casa fica com 10% e o resto é dividido pela tabela
+        assert_eq!(30, balance(&app, TREASURY));
+        let pending = |app: &App, player: &str| {
+            let value: GetPendingRewardsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::GetPendingRewards {
+                        player: Addr::unchecked(player),
+                    },
+                )
+                .unwrap();
+            value.rewards
+        };
+        assert_eq!(coins(189, NATIVE_DENOM), pending(&app, USER2));
+        assert_eq!(coins(81, NATIVE_DENOM), pending(&app, USER1));
+        assert!(pending(&app, USER3).is_empty());
+
+        app.execute(
+            Addr::unchecked(USER2),
+            contract.call(ExecuteMsg::ClaimRewards {}).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(1089, balance(&app, USER2));
+
+        let tournament: GetTournamentResponse = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetTournament { id: 1 })
+            .unwrap();
+        assert_eq!(TournamentState::Settled, tournament.tournament.state);
+        assert_eq!(
+            Coin::new(300, NATIVE_DENOM),
+            tournament.tournament.prize_pool
+        );
+    }
+
+    #[test]
+    fn test_cancelled_tournament_is_refunded() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        let msg = ExecuteMsg::CreateTournament {
+            entry_fee: Coin::new(100, NATIVE_DENOM),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+            max_players: 10,
+            min_players: None,
+            payout_table: vec![Decimal::percent(100)],
+        };
+        app.execute(Addr::unchecked(USER1), contract.call(msg.clone()).unwrap())
+            .unwrap_err();
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(USER1),
+            contract.addr(),
+            &ExecuteMsg::JoinTournament { id: 1 },
+            &coins(100, NATIVE_DENOM),
+        )
+        .unwrap();
+
+        // Enquanto aberto não há reembolso
+        let refund = ExecuteMsg::RefundTournament { id: 1 };
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(refund.clone()).unwrap(),
+        )
+        .unwrap_err();
+
+        let cancel = ExecuteMsg::CancelTournament { id: 1 };
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(cancel.clone()).unwrap(),
+        )
+        .unwrap_err();
+        app.execute(Addr::unchecked(ADMIN), contract.call(cancel).unwrap())
+            .unwrap();
+
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(refund.clone()).unwrap(),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(USER1), contract.call(refund).unwrap())
+            .unwrap_err();
+        let balance = app.wrap().query_balance(USER1, NATIVE_DENOM).unwrap();
+        assert_eq!(1000, balance.amount.u128());
+    }
+
+    #[test]
+    fn test_club_treasury_is_split_among_members() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        let create_club = ExecuteMsg::CreateClub {
+            name: "Tapadores".to_string(),
+            open: true,
+            max_members: 5,
+            scoring: ClubScoring::Sum,
+        };
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(create_club.clone()).unwrap(),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(USER3), contract.call(create_club).unwrap())
+            .unwrap();
+        let msg = ExecuteMsg::JoinClub { id: 1 };
+        app.execute(Addr::unchecked(USER2), contract.call(msg).unwrap())
+            .unwrap();
+
+        // O clube que mais pontuou na temporada leva o prêmio de clubes
+        let msg = ExecuteMsg::CreateSeason {
+            name: "Semana 1".to_string(),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            contract.addr(),
+            &ExecuteMsg::FundSeasonClubPrize { season_id: 1 },
+            &coins(400, NATIVE_DENOM),
+        )
+        .unwrap();
+        app.update_block(|block| block.time = now.plus_seconds(10));
+        for (player, score) in [(USER1, 300), (USER2, 100), (USER3, 50)] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            app.execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+                .unwrap();
+        }
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        let msg = ExecuteMsg::FinalizeSeason { season_id: 1 };
+        app.execute(Addr::unchecked(USER3), contract.call(msg).unwrap())
+            .unwrap();
+
+        let season: GetSeasonResponse = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetSeason { season_id: Some(1) })
+            .unwrap();
+        assert_eq!(Some(1), season.season.club_winner);
+        assert!(season.season.club_prize_pool.is_empty());
+
+        // Qualquer um pode depositar no tesouro de um clube
+        app.execute_contract(
+            Addr::unchecked(USER3),
+            contract.addr(),
+            &ExecuteMsg::DepositToClub { id: 1 },
+            &coins(100, NATIVE_DENOM),
+        )
+        .unwrap();
+        let treasury = |app: &App| {
+            let value: GetClubTreasuryResponse = app
+                .wrap()
+                .query_wasm_smart(contract.addr(), &QueryMsg::GetClubTreasury { id: 1 })
+                .unwrap();
+            value.balance
+        };
+        assert_eq!(coins(500, NATIVE_DENOM), treasury(&app));
+
+        // Só o dono distribui, pela contribuição de cada membro por padrão
+        let distribute = ExecuteMsg::DistributeClubTreasury { id: 1 };
+        app.execute(
+            Addr::unchecked(USER2),
+            contract.call(distribute.clone()).unwrap(),
+        )
+        .unwrap_err();
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(distribute.clone()).unwrap(),
+        )
+        .unwrap();
+        assert!(treasury(&app).is_empty());
+
+        let pending = |app: &App, player: &str| {
+            let value: GetPendingRewardsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::GetPendingRewards {
+                        player: Addr::unchecked(player),
+                    },
+                )
+                .unwrap();
+            value.rewards
+        };
+        assert_eq!(coins(375, NATIVE_DENOM), pending(&app, USER1));
+        assert_eq!(coins(125, NATIVE_DENOM), pending(&app, USER2));
+
+        // Pesos fixos só valem para membros do clube
+        let set_split = |weights: Vec<(&str, u32)>| ExecuteMsg::SetClubSplit {
+            id: 1,
+            split: ClubSplit::Weights {
+                weights: weights
+                    .into_iter()
+                    .map(|(player, weight)| (Addr::unchecked(player), weight))
+                    .collect(),
+            },
+        };
+        app.execute(
+            Addr::unchecked(USER1),
+            contract.call(set_split(vec![(USER3, 1)])).unwrap(),
+        )
+        .unwrap_err();
+        app.execute(
+            Addr::unchecked(USER1),
+            contract
+                .call(set_split(vec![(USER1, 1), (USER2, 2)]))
+                .unwrap(),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            contract.addr(),
+            &ExecuteMsg::DepositToClub { id: 1 },
+            &coins(100, NATIVE_DENOM),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(USER1), contract.call(distribute).unwrap())
+            .unwrap();
+        assert_eq!(coins(191, NATIVE_DENOM), pending(&app, USER2));
+        assert_eq!(coins(1, NATIVE_DENOM), treasury(&app));
+
+        let payouts: GetClubPayoutsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetClubPayouts {
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            vec![2, 1],
+            payouts
+                .payouts
+                .iter()
+                .map(|payout| payout.payout_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                (Addr::unchecked(USER1), coins(33, NATIVE_DENOM)),
+                (Addr::unchecked(USER2), coins(66, NATIVE_DENOM)),
+            ],
+            payouts.payouts[0].shares
+        );
+
+        // O clube não some enquanto tiver fundos no tesouro
+        let leave = ExecuteMsg::LeaveClub {};
+        app.execute(
+            Addr::unchecked(USER2),
+            contract.call(leave.clone()).unwrap(),
+        )
+        .unwrap();
+        app.execute(Addr::unchecked(USER1), contract.call(leave).unwrap())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_season_club_prize_follows_season_scores() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        for player in [USER1, USER3] {
+            let msg = ExecuteMsg::CreateClub {
+                name: player.to_string(),
+                open: true,
+                max_members: 5,
+                scoring: ClubScoring::Sum,
+            };
+            app.execute(Addr::unchecked(player), contract.call(msg).unwrap())
+                .unwrap();
+        }
+        let new_game = |player: &str, score| ExecuteMsg::NewGame {
+            player: Addr::unchecked(player),
+            score,
+            game_time: 60,
+            game_id: None,
+        };
+        app.execute(
+            Addr::unchecked(SUBMITTER),
+            contract.call(new_game(USER1, 500)).unwrap(),
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::CreateSeason {
+            name: "Semana 1".to_string(),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            contract.addr(),
+            &ExecuteMsg::FundSeasonClubPrize { season_id: 1 },
+            &coins(400, NATIVE_DENOM),
+        )
+        .unwrap();
+        app.update_block(|block| block.time = now.plus_seconds(10));
+        for (player, score) in [(USER1, 20), (USER3, 80), (USER2, 1000)] {
+            app.execute(
+                Addr::unchecked(SUBMITTER),
+                contract.call(new_game(player, score)).unwrap(),
+            )
+            .unwrap();
+        }
+
+        // O clube 1 continua na frente no ranking geral
+        let rank: GetClubRankResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetClubRank {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(vec![(500, 1), (80, 2)], rank.rank);
+
+        // Mas o prêmio vai para quem mais pontuou durante a temporada. Quem
+        // entra no clube depois da partida não leva o score junto
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        app.execute(
+            Addr::unchecked(USER2),
+            contract.call(ExecuteMsg::JoinClub { id: 1 }).unwrap(),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::FinalizeSeason { season_id: 1 };
+        app.execute(Addr::unchecked(USER2), contract.call(msg).unwrap())
+            .unwrap();
+        let season: GetSeasonResponse = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetSeason { season_id: Some(1) })
+            .unwrap();
+        assert_eq!(Some(2), season.season.club_winner);
+        let treasury: GetClubTreasuryResponse = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetClubTreasury { id: 2 })
+            .unwrap();
+        assert_eq!(coins(400, NATIVE_DENOM), treasury.balance);
+    }
+
+    #[test]
+    fn test_achievements_are_minted_once() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        let nft_id = app.store_code(cw721_template());
+        let nft = app
+            .instantiate_contract(
+                nft_id,
+                Addr::unchecked(ADMIN),
+                &cw721_base::InstantiateMsg {
+                    name: "Conquistas".to_string(),
+                    symbol: "TAP".to_string(),
+                    minter: contract.addr().to_string(),
+                },
+                &[],
+                "achievements",
+                None,
+            )
+            .unwrap();
+        let msg = ExecuteMsg::SetAchievementNft {
+            nft: Some(nft.to_string()),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+
+        let rules = [
+            AchievementRule::FirstGame,
+            AchievementRule::ScoreAtLeast { score: 150 },
+            AchievementRule::GamesPlayed { games: 2 },
+            AchievementRule::SeasonPodium { top: 1 },
+        ];
+        for (index, rule) in rules.into_iter().enumerate() {
+            let msg = ExecuteMsg::AddAchievement {
+                name: format!("Conquista {}", index + 1),
+                description: "Marco do jogo".to_string(),
+                rule,
+            };
+            app.execute(Addr::unchecked(USER1), contract.call(msg.clone()).unwrap())
+                .unwrap_err();
+            app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        let msg = ExecuteMsg::CreateSeason {
+            name: "Semana 1".to_string(),
+            start: now.plus_seconds(10),
+            end: now.plus_seconds(1000),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        app.update_block(|block| block.time = now.plus_seconds(100));
+
+        // Repetir uma conquista faria o mint falhar no contrato de NFT
+        for (player, score) in [(USER1, 100), (USER1, 200), (USER1, 50), (USER2, 300)] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            app.execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        // O pódio é premiado na finalização da temporada
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        let msg = ExecuteMsg::FinalizeSeason { season_id: 1 };
+        app.execute(Addr::unchecked(USER3), contract.call(msg).unwrap())
+            .unwrap();
+
+        let tokens = |app: &App, player: &str| {
+            let value: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &nft,
+                    &cw721_base::QueryMsg::<Empty>::Tokens {
+                        owner: player.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            value.tokens
+        };
+        assert_eq!(vec!["1:alice", "2:alice", "3:alice"], tokens(&app, USER1));
+        assert_eq!(vec!["1:bob", "2:bob", "4:bob"], tokens(&app, USER2));
+
+        let achievements: GetPlayerAchievementsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetPlayerAchievements {
+                    player: Addr::unchecked(USER2),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            vec![1, 2, 4],
+            achievements
+                .achievements
+                .iter()
+                .map(|achievement| achievement.achievement_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("4:bob", achievements.achievements[2].token_id);
+
+        let info: NftInfoResponse<AchievementMetadata> = app
+            .wrap()
+            .query_wasm_smart(
+                &nft,
+                &cw721_base::QueryMsg::<Empty>::NftInfo {
+                    token_id: "4:bob".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(Some("Conquista 4".to_string()), info.extension.name);
+
+        let list: ListAchievementsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::ListAchievements {
+                    start_after: Some(2),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            vec![
+                AchievementRule::GamesPlayed { games: 2 },
+                AchievementRule::SeasonPodium { top: 1 },
+            ],
+            list.achievements
+                .into_iter()
+                .map(|achievement| achievement.rule)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_failed_mint_keeps_the_game() {
+        let (mut app, contract) = proper_instantiate();
+
+        // O contrato não é minter da coleção, então todo mint falha
+        let nft_id = app.store_code(cw721_template());
+        let nft = app
+            .instantiate_contract(
+                nft_id,
+                Addr::unchecked(ADMIN),
+                &cw721_base::InstantiateMsg {
+                    name: "Conquistas".to_string(),
+                    symbol: "TAP".to_string(),
+                    minter: ADMIN.to_string(),
+                },
+                &[],
+                "achievements",
+                None,
+            )
+            .unwrap();
+        let msg = ExecuteMsg::SetAchievementNft {
+            nft: Some(nft.to_string()),
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+        let msg = ExecuteMsg::AddAchievement {
+            name: "Primeira partida".to_string(),
+            description: "Jogou pela primeira vez".to_string(),
+            rule: AchievementRule::FirstGame,
+        };
+        app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+            .unwrap();
+
+        // A partida é registrada mesmo com o mint recusado
+        let msg = ExecuteMsg::NewGame {
+            player: Addr::unchecked(USER1),
+            score: 100,
+            game_time: 60,
+            game_id: None,
+        };
+        let res = app
+            .execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+            .unwrap();
+        assert!(res.events.iter().any(|event| event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "action" && attr.value == "achievement_mint_failed")));
+
+        let score: GetScoreByPlayerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetScoreByPlayer {
+                    player: Addr::unchecked(USER1),
+                    game_id: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(100, score.score);
+
+        // A conquista fica registrada, sem NFT
+        let achievements: GetPlayerAchievementsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetPlayerAchievements {
+                    player: Addr::unchecked(USER1),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(1, achievements.achievements.len());
+        let tokens: TokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &nft,
+                &cw721_base::QueryMsg::<Empty>::Tokens {
+                    owner: USER1.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(tokens.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_cw20_rewards() {
+        let (mut app, contract) = proper_instantiate();
+        let now = app.block_info().time;
+
+        // Token CW20 real, com o contrato do jogo como minter
+        let cw20_id = app.store_code(cw20_template());
+        let token = app
+            .instantiate_contract(
+                cw20_id,
+                Addr::unchecked(ADMIN),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "Tap Token".to_string(),
+                    symbol: "TAP".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: ADMIN.to_string(),
+                        amount: Uint128::new(1000),
+                    }],
+                    mint: Some(MinterResponse {
+                        minter: contract.addr().to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                },
+                &[],
+                "tap-token",
+                None,
+            )
+            .unwrap();
+
+        for msg in [
+            ExecuteMsg::SetRewardToken {
+                reward_token: token.to_string(),
+            },
+            ExecuteMsg::SetPayoutTable {
+                payout_table: vec![Decimal::percent(60), Decimal::percent(40)],
+            },
+            ExecuteMsg::CreateSeason {
+                name: "Semana 1".to_string(),
+                start: now.plus_seconds(10),
+                end: now.plus_seconds(1000),
+            },
+            // Cada ponto rende um token, cunhado no resgate
+            ExecuteMsg::SetEmission {
+                emission: Some(EmissionSchedule {
+                    tokens_per_point: Decimal::one(),
+                    player_daily_cap: Uint128::new(1000),
+                    daily_budget: Uint128::new(1000),
+                    halving_interval: 7 * 24 * 60 * 60,
+                    start: now,
+                }),
+            },
+        ] {
+            app.execute(Addr::unchecked(ADMIN), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        // O prêmio é financiado com o hook de recebimento do CW20
+        let fund = to_json_binary(&ReceiveMsg::FundSeason { season_id: 1 }).unwrap();
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: contract.addr().to_string(),
+                amount: Uint128::new(1000),
+                msg: fund.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Somente o token configurado é aceito
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER1.to_string(),
+            amount: Uint128::new(1000),
+            msg: fund,
+        });
+        app.execute(Addr::unchecked(USER1), contract.call(msg).unwrap())
+            .unwrap_err();
+
+        app.update_block(|block| block.time = now.plus_seconds(100));
+        for (player, score) in [(USER1, 150), (USER2, 200)] {
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(player),
+                score,
+                game_time: 60,
+                game_id: None,
+            };
+            app.execute(Addr::unchecked(SUBMITTER), contract.call(msg).unwrap())
+                .unwrap();
+        }
+
+        app.update_block(|block| block.time = now.plus_seconds(1000));
+        let msg = ExecuteMsg::FinalizeSeason { season_id: 1 };
+        app.execute(Addr::unchecked(USER3), contract.call(msg).unwrap())
+            .unwrap();
+
+        let token_balance = |app: &App, address: &str| {
+            let value: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token.clone(),
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            value.balance.u128()
+        };
+
+        let pending: GetPendingRewardsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetPendingRewards {
+                    player: Addr::unchecked(USER2),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::new(800), pending.token_rewards);
+        assert!(pending.rewards.is_empty());
+
+        for player in [USER1, USER2] {
+            let msg = ExecuteMsg::ClaimRewards {};
+            app.execute(Addr::unchecked(player), contract.call(msg).unwrap())
+                .unwrap();
+        }
+        assert_eq!(800, token_balance(&app, USER2));
+        assert_eq!(550, token_balance(&app, USER1));
+        assert_eq!(0, token_balance(&app, contract.addr().as_str()));
+    }
+
+    #[test]
+    fn test_new_game_gas_stays_flat() {
+        const GAMES: u64 = 3000;
+        const SAMPLE: u64 = 50;
+
+        let gas = Rc::new(Cell::new(0));
+        let mut app: App<BankKeeper, _, GasMeteredStorage> = AppBuilder::new()
+            .with_storage(GasMeteredStorage {
+                inner: MockStorage::new(),
+                gas: gas.clone(),
+            })
+            .build(|_, _, _| {});
+
+        let contract_id = app.store_code(contract_template());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    submitters: Some(vec![SUBMITTER.to_string()]),
+                    session_limits: trusted_game_time(),
+                    ..Default::default()
+                },
+                &[],
+                "cw-counter",
+                None,
+            )
+            .unwrap();
+        let contract = CwCounterContract(contract_addr);
+
+        // Registra os jogos e mede o gas gasto em cada um
+        let mut costs = Vec::with_capacity(GAMES as usize);
+        for i in 0..GAMES {
+            let player = format!("player{:05}", i);
+            let msg = ExecuteMsg::NewGame {
+                player: Addr::unchecked(&player),
+                score: 1000 + (i * 7919) % 9000,
+                game_time: 60,
+                game_id: None,
+            };
+            let cosmos_msg = contract.call(msg).unwrap();
+
+            let before = gas.get();
+            app.execute(Addr::unchecked(SUBMITTER), cosmos_msg).unwrap();
+            costs.push(gas.get() - before);
+        }
+
+        // O custo médio dos últimos jogos deve ser igual ao dos primeiros
+        let average = |games: &[u64]| games.iter().sum::<u64>() / games.len() as u64;
+        let first = average(&costs[..SAMPLE as usize]);
+        let last = average(&costs[(GAMES - SAMPLE) as usize..]);
+        assert!(
+            last <= first + first / 20,
+            "gas grew from {} to {} after {} games",
+            first,
+            last,
+            GAMES
+        );
+    }
 }